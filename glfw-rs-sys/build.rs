@@ -1,21 +1,159 @@
 use std::path::PathBuf;
 
 fn main() {
+    // loading GLFW at runtime takes precedence over the ways of linking it
+    #[cfg(all(feature = "vendored", not(feature = "dlopen")))]
+    let include_paths = build_vendored();
+    #[cfg(feature = "dlopen")]
+    let include_paths = find_headers();
+    #[cfg(not(any(feature = "vendored", feature = "dlopen")))]
+    let include_paths = probe_system();
+
+    // lets the tests find the header the checked-in bindings are compared with
+    let joined = std::env::join_paths(&include_paths).expect("Include paths should be joinable");
+    println!(
+        "cargo::rustc-env=GLFW_INCLUDE_PATHS={}",
+        joined.to_string_lossy()
+    );
+
+    #[cfg(feature = "bindgen")]
+    generate_bindings(&include_paths);
+    #[cfg(feature = "dlopen")]
+    generate_dynamic();
+}
+
+/// Link to the GLFW found by pkg-config, returns its include paths. With the
+/// `static` feature GLFW itself is always linked statically, the libraries it
+/// depends on are linked the way pkg-config reports
+#[cfg(not(any(feature = "vendored", feature = "dlopen")))]
+fn probe_system() -> Vec<PathBuf> {
+    let statik = cfg!(feature = "static");
+    // pkg-config only links statically outside of the system directories, so
+    // the link flags are printed here instead
     let glfw = pkg_config::Config::new()
+        .cargo_metadata(false)
+        .statik(statik)
         .probe("glfw3")
         .expect("GLFW couldn't be located using pkg-config");
 
-    glfw.libs
+    let mut link_paths = glfw.link_paths.clone();
+    // the system library directory is left out of the link paths
+    if let Ok(libdir) = pkg_config::get_variable("glfw3", "libdir") {
+        link_paths.push(PathBuf::from(libdir));
+    }
+    let msvc = std::env::var("CARGO_CFG_TARGET_ENV").is_ok_and(|env| env == "msvc");
+
+    for path in &link_paths {
+        println!("cargo::rustc-link-search=native={}", path.display());
+    }
+    for path in &glfw.framework_paths {
+        println!("cargo::rustc-link-search=framework={}", path.display());
+    }
+    for lib in &glfw.libs {
+        if statik && lib.starts_with("glfw") {
+            let archive = if msvc {
+                format!("{lib}.lib")
+            } else {
+                format!("lib{lib}.a")
+            };
+            assert!(
+                link_paths.iter().any(|path| path.join(&archive).exists()),
+                "The `static` feature needs {archive}, but it's not in {link_paths:?}"
+            );
+            println!("cargo::rustc-link-lib=static={lib}");
+        } else {
+            println!("cargo::rustc-link-lib={lib}");
+        }
+    }
+    for framework in &glfw.frameworks {
+        println!("cargo::rustc-link-lib=framework={framework}");
+    }
+
+    glfw.include_paths
+}
+
+/// Find the GLFW headers without linking, the library is loaded at runtime and
+/// doesn't have to be installed for building
+#[cfg(feature = "dlopen")]
+fn find_headers() -> Vec<PathBuf> {
+    pkg_config::Config::new()
+        .cargo_metadata(false)
+        .probe("glfw3")
+        .map(|glfw| glfw.include_paths)
+        .unwrap_or_default()
+}
+
+/// Build the bundled GLFW with CMake and link it statically, returns the path
+/// of its headers. The null platform is always built, X11 and Wayland with
+/// their `vendored-*` features, or X11 on Linux and the BSDs when neither is
+/// enabled
+#[cfg(all(feature = "vendored", not(feature = "dlopen")))]
+fn build_vendored() -> Vec<PathBuf> {
+    let source = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap()).join("glfw");
+    assert!(
+        source.join("CMakeLists.txt").exists(),
+        "The bundled GLFW source is missing, run `git submodule update --init`"
+    );
+    println!("cargo::rerun-if-changed={}", source.join("src").display());
+    println!(
+        "cargo::rerun-if-changed={}",
+        source.join("include").display()
+    );
+
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap();
+    let target_family = std::env::var("CARGO_CFG_TARGET_FAMILY").unwrap_or_default();
+    let x11 = cfg!(feature = "vendored-x11");
+    let wayland = cfg!(feature = "vendored-wayland");
+    // without a backend GLFW could only be initialized with the null platform
+    let default_x11 = !x11
+        && !wayland
+        && target_family.split(',').any(|family| family == "unix")
+        && !matches!(target_os.as_str(), "macos" | "ios" | "android");
+
+    let on_off = |enabled| if enabled { "ON" } else { "OFF" };
+    let out = cmake::Config::new(&source)
+        .define("BUILD_SHARED_LIBS", "OFF")
+        .define("GLFW_BUILD_EXAMPLES", "OFF")
+        .define("GLFW_BUILD_TESTS", "OFF")
+        .define("GLFW_BUILD_DOCS", "OFF")
+        .define("GLFW_INSTALL", "ON")
+        .define("GLFW_BUILD_X11", on_off(x11 || default_x11))
+        .define("GLFW_BUILD_WAYLAND", on_off(wayland))
+        .define("CMAKE_INSTALL_LIBDIR", "lib")
+        .build();
+
+    println!(
+        "cargo::rustc-link-search=native={}",
+        out.join("lib").display()
+    );
+    println!("cargo::rustc-link-lib=static=glfw3");
+
+    // GLFW loads the X11 and Wayland libraries at runtime, so only the
+    // libraries it always needs are linked here
+    let system_libs: &[&str] = match target_os.as_str() {
+        "windows" => &["gdi32", "user32", "shell32"],
+        "macos" => &[
+            "framework=Cocoa",
+            "framework=IOKit",
+            "framework=CoreFoundation",
+            "framework=QuartzCore",
+        ],
+        _ => &["m", "dl", "pthread", "rt"],
+    };
+    system_libs
         .iter()
         .for_each(|lib| println!("cargo::rustc-link-lib={lib}"));
-    glfw.link_paths
-        .iter()
-        .for_each(|path| println!("cargo::rustc-link-search={}", path.to_string_lossy()));
 
+    vec![out.join("include")]
+}
+
+/// Generate the bindings into `OUT_DIR`, they are used instead of the checked-in ones
+#[cfg(feature = "bindgen")]
+fn generate_bindings(include_paths: &[PathBuf]) {
     let builder = bindgen::Builder::default()
         .header("wrapper.h")
         .clang_args({
-            glfw.include_paths
+            include_paths
                 .iter()
                 .map(|s| s.to_str().expect("Should always be a valid string"))
                 .map(|s| "-I".to_owned() + s)
@@ -23,8 +161,149 @@ fn main() {
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()));
 
     let bindings = builder.generate().expect("Unable to generate bindings");
-    let out_path = PathBuf::from("./src");
+    let out_path = PathBuf::from(std::env::var("OUT_DIR").expect("Cargo should set OUT_DIR"));
     bindings
         .write_to_file(out_path.join("bindings.rs"))
         .expect("Couldn't write bindings!");
 }
+
+/// Turn the `extern` blocks of the bindings into functions which look up their
+/// symbol in the library loaded at runtime, see `src/dynamic.rs`
+#[cfg(feature = "dlopen")]
+fn generate_dynamic() {
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("Cargo should set OUT_DIR"));
+    let bindings = if cfg!(feature = "bindgen") {
+        out_dir.join("bindings.rs")
+    } else {
+        PathBuf::from("src/bindings.rs")
+    };
+    let sources = [
+        (bindings, "bindings_dynamic.rs"),
+        (PathBuf::from("src/vulkan.rs"), "vulkan_dynamic.rs"),
+        (PathBuf::from("src/native.rs"), "native_dynamic.rs"),
+    ];
+
+    for (source, output) in sources {
+        println!("cargo::rerun-if-changed={}", source.display());
+        let source = std::fs::read_to_string(&source).expect("Couldn't read the bindings!");
+        std::fs::write(out_dir.join(output), dynamic::convert(&source))
+            .expect("Couldn't write the dynamic bindings!");
+    }
+}
+
+#[cfg(feature = "dlopen")]
+mod dynamic {
+    const EXTERN_BLOCK: &str = "unsafe extern \"C\" {";
+
+    pub fn convert(source: &str) -> String {
+        // inner doc comments can't be used in `include!`d files
+        let source: String = source
+            .lines()
+            .filter(|line| !line.starts_with("//!"))
+            .flat_map(|line| [line, "\n"])
+            .collect();
+
+        let mut out = String::new();
+        let mut rest = source.as_str();
+        while let Some(start) = rest.find(EXTERN_BLOCK) {
+            out.push_str(&rest[..start]);
+            let body = &rest[start + EXTERN_BLOCK.len()..];
+            let end = closing_brace(body);
+            convert_block(&body[..end], &mut out);
+            rest = &body[end + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// The position of the `}` closing a block, skipping the braces in strings
+    fn closing_brace(body: &str) -> usize {
+        let (mut depth, mut in_string, mut escaped) = (0, false, false);
+        for (i, c) in body.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                '{' if !in_string => depth += 1,
+                '}' if !in_string && depth == 0 => return i,
+                '}' if !in_string => depth -= 1,
+                _ => (),
+            }
+        }
+        panic!("Unclosed extern block in the bindings!");
+    }
+
+    fn convert_block(mut body: &str, out: &mut String) {
+        while let Some(start) = body.find("pub fn ") {
+            let attributes = body[..start].trim();
+            let signature = &body[start + "pub fn ".len()..];
+            let end = signature
+                .find(';')
+                .expect("Function declarations end with `;`");
+            body = &signature[end + 1..];
+
+            let signature = &signature[..end];
+            let name_end = signature.find('(').expect("Functions have parameters");
+            let name = signature[..name_end].trim();
+            let params_end = name_end + closing_paren(&signature[name_end + 1..]) + 1;
+            let params = &signature[name_end + 1..params_end];
+            let ret = &signature[params_end + 1..];
+            let args = split_params(params)
+                .iter()
+                .map(|param| param.split(':').next().unwrap().trim())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            if !attributes.is_empty() {
+                out.push_str(attributes);
+                out.push('\n');
+            }
+            out.push_str(&format!(
+                "pub unsafe fn {name}({params}){ret} {{
+    type Proc = unsafe extern \"C\" fn({params}){ret};
+    static PROC: ::std::sync::OnceLock<Proc> = ::std::sync::OnceLock::new();
+    let proc = *PROC.get_or_init(|| unsafe {{ crate::dynamic::symbol(b\"{name}\\0\") }});
+    unsafe {{ proc({args}) }}
+}}
+"
+            ));
+        }
+    }
+
+    /// The position of the `)` closing the parameter list
+    fn closing_paren(params: &str) -> usize {
+        let mut depth = 0;
+        for (i, c) in params.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' if depth == 0 => return i,
+                ')' => depth -= 1,
+                _ => (),
+            }
+        }
+        panic!("Unclosed parameter list in the bindings!");
+    }
+
+    /// Split parameters at the commas which aren't part of their types
+    fn split_params(params: &str) -> Vec<&str> {
+        let (mut depth, mut start, mut previous) = (0, 0, ' ');
+        let mut split = Vec::new();
+        for (i, c) in params.char_indices() {
+            match c {
+                '(' | '<' => depth += 1,
+                ')' => depth -= 1,
+                // `->` isn't a closing bracket
+                '>' if previous != '-' => depth -= 1,
+                ',' if depth == 0 => {
+                    split.push(&params[start..i]);
+                    start = i + 1;
+                }
+                _ => (),
+            }
+            previous = c;
+        }
+        split.push(&params[start..]);
+        split.retain(|param| !param.trim().is_empty());
+        split
+    }
+}
@@ -0,0 +1,61 @@
+//! Runtime loading of GLFW, used instead of linking with the `dlopen` feature.
+//! Every GLFW function looks up its symbol in the loaded library on its first
+//! call, so the API is the same as when linking.
+
+use libloading::Library;
+use std::sync::OnceLock;
+
+/// The names of the GLFW library tried by [`load`], in order
+#[cfg(target_os = "windows")]
+pub const LIBRARY_NAMES: &[&str] = &["glfw3.dll"];
+#[cfg(target_os = "macos")]
+pub const LIBRARY_NAMES: &[&str] = &["libglfw.3.dylib", "libglfw.dylib"];
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+pub const LIBRARY_NAMES: &[&str] = &["libglfw.so.3", "libglfw.so"];
+
+static LIBRARY: OnceLock<Result<Library, String>> = OnceLock::new();
+
+/// Load the GLFW library, or get the result of the first attempt if it was
+/// already loaded. Must succeed before calling any GLFW function.
+pub fn load() -> Result<(), String> {
+    LIBRARY
+        .get_or_init(open)
+        .as_ref()
+        .map(|_| ())
+        .map_err(Clone::clone)
+}
+
+/// Check if the GLFW library was loaded successfully
+pub fn is_loaded() -> bool {
+    LIBRARY.get().is_some_and(Result::is_ok)
+}
+
+fn open() -> Result<Library, String> {
+    let mut errors = Vec::new();
+    for name in LIBRARY_NAMES {
+        match unsafe { Library::new(name) } {
+            Ok(library) => return Ok(library),
+            Err(err) => errors.push(err.to_string()),
+        }
+    }
+    Err(errors.join("; "))
+}
+
+/// Get a function from the GLFW library.
+///
+/// # Panics
+/// If the library can't be loaded or doesn't have the function, e.g. when it's
+/// older than the bindings
+pub(crate) unsafe fn symbol<T: Copy>(name: &[u8]) -> T {
+    let library = match LIBRARY.get_or_init(open) {
+        Ok(library) => library,
+        Err(err) => panic!("the GLFW library couldn't be loaded: {err}"),
+    };
+    match unsafe { library.get::<T>(name) } {
+        Ok(symbol) => *symbol,
+        Err(err) => panic!(
+            "the GLFW library doesn't have `{}`: {err}",
+            String::from_utf8_lossy(name.strip_suffix(b"\0").unwrap_or(name))
+        ),
+    }
+}
@@ -2,5 +2,73 @@
 #![allow(non_camel_case_types)]
 #![allow(non_snake_case)]
 
+#[cfg(not(any(feature = "bindgen", feature = "dlopen")))]
 mod bindings;
+#[cfg(all(feature = "bindgen", not(feature = "dlopen")))]
+mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+#[cfg(feature = "dlopen")]
+#[allow(clippy::missing_safety_doc, clippy::too_many_arguments)]
+mod bindings {
+    include!(concat!(env!("OUT_DIR"), "/bindings_dynamic.rs"));
+}
 pub use bindings::*;
+
+#[cfg(feature = "dlopen")]
+mod dynamic;
+#[cfg(feature = "dlopen")]
+pub use dynamic::{LIBRARY_NAMES, is_loaded, load};
+
+#[cfg(all(feature = "vulkan", not(feature = "dlopen")))]
+mod vulkan;
+#[cfg(all(feature = "vulkan", feature = "dlopen"))]
+#[allow(clippy::missing_safety_doc)]
+mod vulkan {
+    include!(concat!(env!("OUT_DIR"), "/vulkan_dynamic.rs"));
+}
+#[cfg(feature = "vulkan")]
+pub use vulkan::*;
+
+#[cfg(all(
+    any(
+        all(
+            unix,
+            not(target_os = "macos"),
+            any(feature = "x11", feature = "wayland")
+        ),
+        feature = "glx",
+        feature = "egl",
+        feature = "osmesa"
+    ),
+    not(feature = "dlopen")
+))]
+mod native;
+#[cfg(all(
+    any(
+        all(
+            unix,
+            not(target_os = "macos"),
+            any(feature = "x11", feature = "wayland")
+        ),
+        feature = "glx",
+        feature = "egl",
+        feature = "osmesa"
+    ),
+    feature = "dlopen"
+))]
+#[allow(clippy::missing_safety_doc)]
+mod native {
+    include!(concat!(env!("OUT_DIR"), "/native_dynamic.rs"));
+}
+#[cfg(any(
+    all(
+        unix,
+        not(target_os = "macos"),
+        any(feature = "x11", feature = "wayland")
+    ),
+    feature = "glx",
+    feature = "egl",
+    feature = "osmesa"
+))]
+pub use native::*;
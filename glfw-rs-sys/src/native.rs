@@ -0,0 +1,118 @@
+//! Native access functions from `glfw3native.h`. The header needs the platform
+//! headers to be included first, so these are declared by hand with the native
+//! handles as opaque types. Every backend has its own feature, and the functions
+//! are only available when GLFW was built with that backend. X11 and Wayland
+//! are only declared on Unix other than macOS, so enabling them elsewhere links.
+
+use crate::GLFWwindow;
+
+#[cfg(all(feature = "x11", unix, not(target_os = "macos")))]
+#[repr(C)]
+pub struct Display {
+    _unused: [u8; 0],
+}
+/// An Xlib `XID`
+#[cfg(all(feature = "x11", unix, not(target_os = "macos")))]
+pub type Window = ::std::os::raw::c_ulong;
+
+#[cfg(all(feature = "x11", unix, not(target_os = "macos")))]
+unsafe extern "C" {
+    pub fn glfwGetX11Display() -> *mut Display;
+}
+#[cfg(all(feature = "x11", unix, not(target_os = "macos")))]
+unsafe extern "C" {
+    pub fn glfwGetX11Window(window: *mut GLFWwindow) -> Window;
+}
+
+#[cfg(all(feature = "wayland", unix, not(target_os = "macos")))]
+#[repr(C)]
+pub struct wl_display {
+    _unused: [u8; 0],
+}
+#[cfg(all(feature = "wayland", unix, not(target_os = "macos")))]
+#[repr(C)]
+pub struct wl_surface {
+    _unused: [u8; 0],
+}
+
+#[cfg(all(feature = "wayland", unix, not(target_os = "macos")))]
+unsafe extern "C" {
+    pub fn glfwGetWaylandDisplay() -> *mut wl_display;
+}
+#[cfg(all(feature = "wayland", unix, not(target_os = "macos")))]
+unsafe extern "C" {
+    pub fn glfwGetWaylandWindow(window: *mut GLFWwindow) -> *mut wl_surface;
+}
+
+#[cfg(feature = "glx")]
+#[repr(C)]
+pub struct __GLXcontextRec {
+    _unused: [u8; 0],
+}
+#[cfg(feature = "glx")]
+pub type GLXContext = *mut __GLXcontextRec;
+/// A GLX `XID`
+#[cfg(feature = "glx")]
+pub type GLXWindow = ::std::os::raw::c_ulong;
+
+#[cfg(feature = "glx")]
+unsafe extern "C" {
+    pub fn glfwGetGLXContext(window: *mut GLFWwindow) -> GLXContext;
+}
+#[cfg(feature = "glx")]
+unsafe extern "C" {
+    pub fn glfwGetGLXWindow(window: *mut GLFWwindow) -> GLXWindow;
+}
+
+#[cfg(feature = "egl")]
+pub type EGLDisplay = *mut ::std::os::raw::c_void;
+#[cfg(feature = "egl")]
+pub type EGLContext = *mut ::std::os::raw::c_void;
+#[cfg(feature = "egl")]
+pub type EGLSurface = *mut ::std::os::raw::c_void;
+
+#[cfg(feature = "egl")]
+unsafe extern "C" {
+    pub fn glfwGetEGLDisplay() -> EGLDisplay;
+}
+#[cfg(feature = "egl")]
+unsafe extern "C" {
+    pub fn glfwGetEGLContext(window: *mut GLFWwindow) -> EGLContext;
+}
+#[cfg(feature = "egl")]
+unsafe extern "C" {
+    pub fn glfwGetEGLSurface(window: *mut GLFWwindow) -> EGLSurface;
+}
+
+#[cfg(feature = "osmesa")]
+#[repr(C)]
+pub struct osmesa_context {
+    _unused: [u8; 0],
+}
+#[cfg(feature = "osmesa")]
+pub type OSMesaContext = *mut osmesa_context;
+
+#[cfg(feature = "osmesa")]
+unsafe extern "C" {
+    pub fn glfwGetOSMesaColorBuffer(
+        window: *mut GLFWwindow,
+        width: *mut ::std::os::raw::c_int,
+        height: *mut ::std::os::raw::c_int,
+        format: *mut ::std::os::raw::c_int,
+        buffer: *mut *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+#[cfg(feature = "osmesa")]
+unsafe extern "C" {
+    pub fn glfwGetOSMesaDepthBuffer(
+        window: *mut GLFWwindow,
+        width: *mut ::std::os::raw::c_int,
+        height: *mut ::std::os::raw::c_int,
+        bytesPerValue: *mut ::std::os::raw::c_int,
+        buffer: *mut *mut ::std::os::raw::c_void,
+    ) -> ::std::os::raw::c_int;
+}
+#[cfg(feature = "osmesa")]
+unsafe extern "C" {
+    pub fn glfwGetOSMesaContext(window: *mut GLFWwindow) -> OSMesaContext;
+}
@@ -0,0 +1,48 @@
+//! Vulkan functions of GLFW. `wrapper.h` doesn't include the Vulkan headers, so
+//! bindgen skips every function using Vulkan types and they are declared here,
+//! with the Vulkan handles as opaque types.
+
+use crate::{GLFWvkproc, GLFWwindow};
+
+#[repr(C)]
+pub struct VkInstance_T {
+    _unused: [u8; 0],
+}
+pub type VkInstance = *mut VkInstance_T;
+
+#[repr(C)]
+pub struct VkPhysicalDevice_T {
+    _unused: [u8; 0],
+}
+pub type VkPhysicalDevice = *mut VkPhysicalDevice_T;
+
+/// Non-dispatchable handles are always 64 bits wide
+pub type VkSurfaceKHR = u64;
+pub type VkResult = ::std::os::raw::c_int;
+
+#[repr(C)]
+pub struct VkAllocationCallbacks {
+    _unused: [u8; 0],
+}
+
+unsafe extern "C" {
+    pub fn glfwGetInstanceProcAddress(
+        instance: VkInstance,
+        procname: *const ::std::os::raw::c_char,
+    ) -> GLFWvkproc;
+}
+unsafe extern "C" {
+    pub fn glfwGetPhysicalDevicePresentationSupport(
+        instance: VkInstance,
+        device: VkPhysicalDevice,
+        queuefamily: u32,
+    ) -> ::std::os::raw::c_int;
+}
+unsafe extern "C" {
+    pub fn glfwCreateWindowSurface(
+        instance: VkInstance,
+        window: *mut GLFWwindow,
+        allocator: *const VkAllocationCallbacks,
+        surface: *mut VkSurfaceKHR,
+    ) -> VkResult;
+}
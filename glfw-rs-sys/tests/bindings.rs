@@ -0,0 +1,56 @@
+//! The checked-in bindings are generated from a specific GLFW header, make sure
+//! it's the same version as the GLFW being linked.
+
+use std::{env, fs, path::PathBuf};
+
+const BINDINGS: &str = include_str!("../src/bindings.rs");
+
+fn find_header(include_paths: &str) -> Option<String> {
+    let defaults = ["/usr/include", "/usr/local/include"].map(PathBuf::from);
+    env::split_paths(include_paths)
+        .chain(defaults)
+        .find_map(|path| fs::read_to_string(path.join("GLFW/glfw3.h")).ok())
+}
+
+fn header_define(header: &str, name: &str) -> u32 {
+    header
+        .lines()
+        .find_map(|line| {
+            let mut words = line.split_whitespace();
+            (words.next()? == "#define" && words.next()? == name).then_some(words.next()?)
+        })
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| panic!("{name} should be defined in glfw3.h"))
+}
+
+fn bindings_const(name: &str) -> u32 {
+    let prefix = format!("pub const {name}: u32 = ");
+    BINDINGS
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix)?.strip_suffix(';'))
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| panic!("{name} should be in the checked-in bindings"))
+}
+
+#[test]
+fn checked_in_bindings_match_header_version() {
+    let include_paths = env!("GLFW_INCLUDE_PATHS");
+    if cfg!(feature = "dlopen") && include_paths.is_empty() {
+        // GLFW doesn't have to be installed when it's loaded at runtime
+        return;
+    }
+
+    let header = find_header(include_paths)
+        .expect("glfw3.h should be in the include paths reported by pkg-config");
+    for name in [
+        "GLFW_VERSION_MAJOR",
+        "GLFW_VERSION_MINOR",
+        "GLFW_VERSION_REVISION",
+    ] {
+        assert_eq!(
+            bindings_const(name),
+            header_define(&header, name),
+            "the checked-in bindings don't match glfw3.h, regenerate them with the `bindgen` feature"
+        );
+    }
+}
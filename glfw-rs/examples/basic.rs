@@ -1,7 +1,7 @@
 use glfw_rs::Context;
 
 fn main() {
-    let ctx = Context::init();
+    let ctx = Context::init().expect("Cannot initialize GLFW!");
     ctx.gl_version(3, 3);
     let window = ctx
         .window("Hello world", (640, 480))
@@ -0,0 +1,25 @@
+use glfw_rs::{Context, ContextCreationApi};
+
+fn main() {
+    let ctx = Context::init().expect("Cannot initialize GLFW!");
+    ctx.context_creation_api(ContextCreationApi::OsMesa);
+    let window = ctx
+        .window("OSMesa", (320, 240))
+        .expect("Cannot create the window!");
+
+    window.make_global();
+    gl::load_with(window.get_safe_load_proc());
+    unsafe {
+        gl::ClearColor(0.1, 0.5, 1.0, 1.0);
+        gl::Clear(gl::COLOR_BUFFER_BIT);
+        gl::Finish();
+    }
+
+    let buffer = window
+        .osmesa_color_buffer()
+        .expect("The context wasn't created with OSMesa!");
+    println!("Rendered {}x{}", buffer.width(), buffer.height());
+
+    let image: image::RgbaImage = buffer.into();
+    image.save("osmesa.png").expect("Cannot save the image!");
+}
@@ -0,0 +1,37 @@
+use glfw_rs::Context;
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+fn main() {
+    let ctx = Context::init().expect("Cannot initialize GLFW!");
+    ctx.gl_version(3, 3);
+    let window = ctx
+        .window("Render thread", (640, 480))
+        .expect("Cannot create the window!");
+
+    let render = window
+        .render_context()
+        .expect("The context is not split yet");
+    let running = &AtomicBool::new(true);
+
+    thread::scope(|scope| {
+        scope.spawn(move || {
+            render.make_current();
+            render.set_swap_interval(1);
+            gl::load_with(glfw_rs::safe_load_proc);
+            unsafe { gl::ClearColor(0.1, 0.5, 1.0, 1.0) };
+
+            while running.load(Ordering::Relaxed) {
+                unsafe { gl::Clear(gl::COLOR_BUFFER_BIT) };
+                render.swap_buffers();
+            }
+        });
+
+        while !window.should_close() {
+            ctx.wait_events();
+        }
+        running.store(false, Ordering::Relaxed);
+    });
+}
@@ -0,0 +1,38 @@
+use glfw_rs::{Context, ControlFlow, Event};
+use std::{thread, time::Duration};
+
+fn main() {
+    let ctx = Context::init().expect("Cannot initialize GLFW!");
+    ctx.gl_version(3, 3);
+    let window = ctx
+        .window("Run loop", (640, 480))
+        .expect("Cannot create the window!");
+    window.make_global();
+
+    gl::load_with(window.get_safe_load_proc());
+    unsafe { gl::ClearColor(0.1, 0.5, 1.0, 1.0) };
+
+    let proxy = ctx.create_proxy::<&'static str>();
+    thread::spawn(move || {
+        thread::sleep(Duration::from_secs(1));
+        proxy.send_event("Hello from another thread!");
+    });
+
+    ctx.run(|event, control| {
+        *control = ControlFlow::Wait;
+
+        match event {
+            Event::RedrawRequested { .. } => {
+                unsafe { gl::Clear(gl::COLOR_BUFFER_BIT) };
+                window.update();
+            }
+            Event::Refresh { .. } | Event::FramebufferResized { .. } => window.request_redraw(),
+            Event::User(message) => {
+                if let Ok(message) = message.downcast::<&'static str>() {
+                    println!("{message}");
+                }
+            }
+            _ => (),
+        }
+    });
+}
@@ -8,7 +8,7 @@ unsafe extern "C" fn mouse_callback(window: *mut GLFWwindow, x: f64, y: f64) {
 }
 
 fn main() {
-    let ctx = Context::init();
+    let ctx = Context::init().expect("Cannot initialize GLFW!");
     ctx.gl_version(3, 3);
     let window = ctx
         .window("User data", (640, 480))
@@ -0,0 +1,39 @@
+use ash::vk;
+use glfw_rs::Context;
+
+fn main() {
+    let ctx = Context::init().expect("Cannot initialize GLFW!");
+    assert!(ctx.vulkan_supported(), "Vulkan is not available!");
+
+    let extensions = ctx
+        .required_instance_extensions()
+        .expect("Vulkan cannot present to windows!");
+    println!("Required extensions: {extensions:?}");
+    let extensions: Vec<_> = extensions.iter().map(|name| name.as_ptr()).collect();
+
+    let entry = unsafe { ash::Entry::load() }.expect("Cannot load Vulkan!");
+    let app_info = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_0);
+    let create_info = vk::InstanceCreateInfo::default()
+        .application_info(&app_info)
+        .enabled_extension_names(&extensions);
+    let instance =
+        unsafe { entry.create_instance(&create_info, None) }.expect("Cannot create the instance!");
+
+    let window = ctx
+        .window_no_api("Vulkan", (640, 480))
+        .expect("Cannot create the window!");
+    let surface = window
+        .create_surface(&instance, None)
+        .expect("Cannot create the surface!");
+    println!("Created surface {surface:?}");
+
+    while !window.should_close() {
+        ctx.wait_events();
+    }
+
+    let surface_instance = ash::khr::surface::Instance::new(&entry, &instance);
+    unsafe {
+        surface_instance.destroy_surface(surface, None);
+        instance.destroy_instance(None);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::Context;
+use glfw_rs_sys as glfw_sys;
+use glfw_sys::GLFWallocator;
+use std::{
+    alloc::{self, GlobalAlloc, Layout},
+    ffi::c_void,
+    ptr,
+    sync::{
+        OnceLock,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+static STATE: OnceLock<AllocatorState> = OnceLock::new();
+
+/// Every block starts with its size, as GLFW doesn't pass it when freeing. This
+/// is also the alignment of the blocks, enough for any C type.
+const HEADER: usize = 16;
+
+/// The allocator GLFW should use for its own memory, see
+/// [`InitOptions::allocator`].
+///
+/// [`InitOptions::allocator`]: crate::InitOptions::allocator
+#[derive(Clone, Copy)]
+pub struct Allocator {
+    alloc: &'static (dyn GlobalAlloc + Sync),
+    accounting: bool,
+}
+
+impl Allocator {
+    /// Route GLFW's allocations through Rust's global allocator
+    pub fn global() -> Self {
+        Self::new(&RustGlobal)
+    }
+
+    /// Route GLFW's allocations through `alloc`
+    pub fn new(alloc: &'static (dyn GlobalAlloc + Sync)) -> Self {
+        Self {
+            alloc,
+            accounting: false,
+        }
+    }
+
+    /// Keep track of GLFW's memory, see [`Context::allocation_stats`]
+    pub fn with_accounting(mut self) -> Self {
+        self.accounting = true;
+        self
+    }
+}
+
+/// A snapshot of GLFW's memory use, see [`Context::allocation_stats`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AllocationStats {
+    /// The bytes GLFW has allocated and not freed yet
+    pub current_bytes: usize,
+    /// The most bytes GLFW had allocated at once
+    pub peak_bytes: usize,
+    /// The blocks GLFW has allocated and not freed yet
+    pub live_allocations: usize,
+    /// The blocks GLFW has allocated in total, including reallocations
+    pub total_allocations: usize,
+}
+
+/// Forwards to whichever allocator is the `#[global_allocator]`
+struct RustGlobal;
+
+unsafe impl GlobalAlloc for RustGlobal {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        unsafe { alloc::alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { alloc::dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        unsafe { alloc::realloc(ptr, layout, new_size) }
+    }
+}
+
+struct AllocatorState {
+    alloc: &'static (dyn GlobalAlloc + Sync),
+    accounting: bool,
+    current_bytes: AtomicUsize,
+    peak_bytes: AtomicUsize,
+    live_allocations: AtomicUsize,
+    total_allocations: AtomicUsize,
+}
+
+impl AllocatorState {
+    fn record_alloc(&self, size: usize) {
+        if self.accounting {
+            let current = self.current_bytes.fetch_add(size, Ordering::Relaxed) + size;
+            self.peak_bytes.fetch_max(current, Ordering::Relaxed);
+            self.live_allocations.fetch_add(1, Ordering::Relaxed);
+            self.total_allocations.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn record_dealloc(&self, size: usize) {
+        if self.accounting {
+            self.current_bytes.fetch_sub(size, Ordering::Relaxed);
+            self.live_allocations.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+fn layout(size: usize) -> Option<Layout> {
+    Layout::from_size_align(size.checked_add(HEADER)?, HEADER).ok()
+}
+
+/// Get the size of a block returned to GLFW and the start of its allocation
+unsafe fn block_start(block: *mut c_void) -> (*mut u8, usize) {
+    let start = unsafe { block.cast::<u8>().sub(HEADER) };
+    (start, unsafe { start.cast::<usize>().read() })
+}
+
+unsafe extern "C" fn allocate(size: usize, user: *mut c_void) -> *mut c_void {
+    let state = unsafe { &*user.cast::<AllocatorState>() };
+    let Some(layout) = layout(size) else {
+        return ptr::null_mut();
+    };
+
+    let start = unsafe { state.alloc.alloc(layout) };
+    if start.is_null() {
+        return ptr::null_mut();
+    }
+    unsafe { start.cast::<usize>().write(size) };
+    state.record_alloc(size);
+    unsafe { start.add(HEADER).cast() }
+}
+
+unsafe extern "C" fn reallocate(block: *mut c_void, size: usize, user: *mut c_void) -> *mut c_void {
+    let state = unsafe { &*user.cast::<AllocatorState>() };
+    let (start, old_size) = unsafe { block_start(block) };
+    let (Some(old_layout), Some(_)) = (layout(old_size), layout(size)) else {
+        return ptr::null_mut();
+    };
+
+    let start = unsafe { state.alloc.realloc(start, old_layout, size + HEADER) };
+    if start.is_null() {
+        return ptr::null_mut();
+    }
+    unsafe { start.cast::<usize>().write(size) };
+    state.record_dealloc(old_size);
+    state.record_alloc(size);
+    unsafe { start.add(HEADER).cast() }
+}
+
+unsafe extern "C" fn deallocate(block: *mut c_void, user: *mut c_void) {
+    let state = unsafe { &*user.cast::<AllocatorState>() };
+    let (start, size) = unsafe { block_start(block) };
+    let layout = layout(size).expect("the layout was valid when allocating");
+    unsafe { state.alloc.dealloc(start, layout) };
+    state.record_dealloc(size);
+}
+
+/// Make GLFW use `allocator`, must be called before `glfwInit`. The first
+/// allocator stays in use, as GLFW may still hold memory from it
+pub(crate) fn install(allocator: Allocator) {
+    let state = STATE.get_or_init(|| AllocatorState {
+        alloc: allocator.alloc,
+        accounting: allocator.accounting,
+        current_bytes: AtomicUsize::new(0),
+        peak_bytes: AtomicUsize::new(0),
+        live_allocations: AtomicUsize::new(0),
+        total_allocations: AtomicUsize::new(0),
+    });
+
+    // GLFW copies the struct, the state is static
+    let raw = GLFWallocator {
+        allocate: Some(allocate),
+        reallocate: Some(reallocate),
+        deallocate: Some(deallocate),
+        user: ptr::from_ref(state).cast_mut().cast(),
+    };
+    unsafe { glfw_sys::glfwInitAllocator(&raw) };
+}
+
+impl Context {
+    /// Get how much memory GLFW is using. Returns [`None`] unless GLFW was
+    /// initialized with an [`Allocator`] with accounting enabled
+    pub fn allocation_stats(&self) -> Option<AllocationStats> {
+        let state = STATE.get().filter(|state| state.accounting)?;
+        Some(AllocationStats {
+            current_bytes: state.current_bytes.load(Ordering::Relaxed),
+            peak_bytes: state.peak_bytes.load(Ordering::Relaxed),
+            live_allocations: state.live_allocations.load(Ordering::Relaxed),
+            total_allocations: state.total_allocations.load(Ordering::Relaxed),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(accounting: bool) -> AllocatorState {
+        AllocatorState {
+            alloc: &RustGlobal,
+            accounting,
+            current_bytes: AtomicUsize::new(0),
+            peak_bytes: AtomicUsize::new(0),
+            live_allocations: AtomicUsize::new(0),
+            total_allocations: AtomicUsize::new(0),
+        }
+    }
+
+    fn stats(state: &AllocatorState) -> AllocationStats {
+        AllocationStats {
+            current_bytes: state.current_bytes.load(Ordering::Relaxed),
+            peak_bytes: state.peak_bytes.load(Ordering::Relaxed),
+            live_allocations: state.live_allocations.load(Ordering::Relaxed),
+            total_allocations: state.total_allocations.load(Ordering::Relaxed),
+        }
+    }
+
+    fn expected(current: usize, peak: usize, live: usize, total: usize) -> AllocationStats {
+        AllocationStats {
+            current_bytes: current,
+            peak_bytes: peak,
+            live_allocations: live,
+            total_allocations: total,
+        }
+    }
+
+    #[test]
+    fn allocations_are_counted() {
+        let state = state(true);
+        let user = ptr::from_ref(&state).cast_mut().cast();
+
+        let a = unsafe { allocate(24, user) };
+        let b = unsafe { allocate(100, user) };
+        assert!(!a.is_null() && !b.is_null());
+        assert_eq!(a as usize % HEADER, 0);
+        assert_eq!(stats(&state), expected(124, 124, 2, 2));
+
+        unsafe { deallocate(a, user) };
+        assert_eq!(stats(&state), expected(100, 124, 1, 2));
+        unsafe { deallocate(b, user) };
+        assert_eq!(stats(&state), expected(0, 124, 0, 2));
+    }
+
+    #[test]
+    fn reallocations_keep_the_contents() {
+        let state = state(true);
+        let user = ptr::from_ref(&state).cast_mut().cast();
+
+        let block = unsafe { allocate(8, user) };
+        unsafe { block.cast::<u64>().write(0x0123_4567_89ab_cdef) };
+
+        // growing replaces the old size and counts as another allocation
+        let block = unsafe { reallocate(block, 64, user) };
+        assert!(!block.is_null());
+        assert_eq!(unsafe { block.cast::<u64>().read() }, 0x0123_4567_89ab_cdef);
+        assert_eq!(stats(&state), expected(64, 64, 1, 2));
+
+        // shrinking lowers the current bytes but not the peak
+        let block = unsafe { reallocate(block, 16, user) };
+        assert!(!block.is_null());
+        assert_eq!(unsafe { block.cast::<u64>().read() }, 0x0123_4567_89ab_cdef);
+        assert_eq!(stats(&state), expected(16, 64, 1, 3));
+
+        unsafe { deallocate(block, user) };
+        assert_eq!(stats(&state), expected(0, 64, 0, 3));
+    }
+
+    #[test]
+    fn nothing_is_counted_without_accounting() {
+        let state = state(false);
+        let user = ptr::from_ref(&state).cast_mut().cast();
+
+        let block = unsafe { allocate(32, user) };
+        let block = unsafe { reallocate(block, 48, user) };
+        unsafe { deallocate(block, user) };
+        assert_eq!(stats(&state), AllocationStats::default());
+    }
+
+    #[test]
+    fn oversized_allocations_fail() {
+        let state = state(true);
+        let user = ptr::from_ref(&state).cast_mut().cast();
+
+        assert!(unsafe { allocate(usize::MAX, user) }.is_null());
+        assert_eq!(stats(&state), AllocationStats::default());
+    }
+}
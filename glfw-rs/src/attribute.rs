@@ -0,0 +1,186 @@
+use glfw_rs_sys as glfw_sys;
+
+/// A boolean attribute of a window which can be queried using
+/// [`Window::get_attribute`]. The attributes with other values have their own
+/// getters, see [`ValueAttribute`]
+///
+/// [`Window::get_attribute`]: crate::Window::get_attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum WindowAttribute {
+    Focused = glfw_sys::GLFW_FOCUSED,
+    Iconified = glfw_sys::GLFW_ICONIFIED,
+    Maximized = glfw_sys::GLFW_MAXIMIZED,
+    Visible = glfw_sys::GLFW_VISIBLE,
+    Hovered = glfw_sys::GLFW_HOVERED,
+    Resizable = glfw_sys::GLFW_RESIZABLE,
+    Decorated = glfw_sys::GLFW_DECORATED,
+    AutoIconify = glfw_sys::GLFW_AUTO_ICONIFY,
+    Floating = glfw_sys::GLFW_FLOATING,
+    FocusOnShow = glfw_sys::GLFW_FOCUS_ON_SHOW,
+    MousePassthrough = glfw_sys::GLFW_MOUSE_PASSTHROUGH,
+    TransparentFramebuffer = glfw_sys::GLFW_TRANSPARENT_FRAMEBUFFER,
+    ContextNoError = glfw_sys::GLFW_CONTEXT_NO_ERROR,
+    ContextDebug = glfw_sys::GLFW_CONTEXT_DEBUG,
+    OpenGlForwardCompat = glfw_sys::GLFW_OPENGL_FORWARD_COMPAT,
+    DoubleBuffer = glfw_sys::GLFW_DOUBLEBUFFER,
+}
+
+/// An integer attribute of a window which can be queried using
+/// [`Window::get_value`]. The attributes with an enum value are queried with
+/// [`Window::client_api`], [`Window::context_creation_api`],
+/// [`Window::opengl_profile`], [`Window::context_robustness`] and
+/// [`Window::release_behavior`]
+///
+/// [`Window::get_value`]: crate::Window::get_value
+/// [`Window::client_api`]: crate::Window::client_api
+/// [`Window::context_creation_api`]: crate::Window::context_creation_api
+/// [`Window::opengl_profile`]: crate::Window::opengl_profile
+/// [`Window::context_robustness`]: crate::Window::context_robustness
+/// [`Window::release_behavior`]: crate::Window::release_behavior
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ValueAttribute {
+    ContextVersionMajor = glfw_sys::GLFW_CONTEXT_VERSION_MAJOR,
+    ContextVersionMinor = glfw_sys::GLFW_CONTEXT_VERSION_MINOR,
+    ContextRevision = glfw_sys::GLFW_CONTEXT_REVISION,
+}
+
+/// The subset of [`WindowAttribute`] which can be changed after the window was
+/// created, using [`Window::set_attribute`].
+///
+/// [`Window::set_attribute`]: crate::Window::set_attribute
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum SettableAttribute {
+    Resizable = glfw_sys::GLFW_RESIZABLE,
+    Decorated = glfw_sys::GLFW_DECORATED,
+    AutoIconify = glfw_sys::GLFW_AUTO_ICONIFY,
+    Floating = glfw_sys::GLFW_FLOATING,
+    FocusOnShow = glfw_sys::GLFW_FOCUS_ON_SHOW,
+    MousePassthrough = glfw_sys::GLFW_MOUSE_PASSTHROUGH,
+}
+
+impl From<SettableAttribute> for WindowAttribute {
+    fn from(value: SettableAttribute) -> Self {
+        match value {
+            SettableAttribute::Resizable => Self::Resizable,
+            SettableAttribute::Decorated => Self::Decorated,
+            SettableAttribute::AutoIconify => Self::AutoIconify,
+            SettableAttribute::Floating => Self::Floating,
+            SettableAttribute::FocusOnShow => Self::FocusOnShow,
+            SettableAttribute::MousePassthrough => Self::MousePassthrough,
+        }
+    }
+}
+
+/// The client API of a window's context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ClientApi {
+    /// The window has no context, e.g. when using Vulkan
+    None = glfw_sys::GLFW_NO_API,
+    OpenGl = glfw_sys::GLFW_OPENGL_API,
+    OpenGlEs = glfw_sys::GLFW_OPENGL_ES_API,
+}
+
+/// The API used to create a window's context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ContextCreationApi {
+    Native = glfw_sys::GLFW_NATIVE_CONTEXT_API,
+    Egl = glfw_sys::GLFW_EGL_CONTEXT_API,
+    OsMesa = glfw_sys::GLFW_OSMESA_CONTEXT_API,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum OpenGlProfile {
+    /// The profile is unknown, or the context is an OpenGL ES one or older than 3.2
+    Any = glfw_sys::GLFW_OPENGL_ANY_PROFILE,
+    Core = glfw_sys::GLFW_OPENGL_CORE_PROFILE,
+    Compat = glfw_sys::GLFW_OPENGL_COMPAT_PROFILE,
+}
+
+/// The robustness strategy of a window's context
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ContextRobustness {
+    None = glfw_sys::GLFW_NO_ROBUSTNESS,
+    NoResetNotification = glfw_sys::GLFW_NO_RESET_NOTIFICATION,
+    LoseContextOnReset = glfw_sys::GLFW_LOSE_CONTEXT_ON_RESET,
+}
+
+/// Whether a window's context is flushed when it's no longer current
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ReleaseBehavior {
+    /// The default behavior of the context creation API
+    Any = glfw_sys::GLFW_ANY_RELEASE_BEHAVIOR,
+    Flush = glfw_sys::GLFW_RELEASE_BEHAVIOR_FLUSH,
+    None = glfw_sys::GLFW_RELEASE_BEHAVIOR_NONE,
+}
+
+/// The properties of the context a window was actually created with, which may
+/// differ from the requested ones. See [`Window::context_info`]
+///
+/// [`Window::context_info`]: crate::Window::context_info
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ContextInfo {
+    pub client_api: ClientApi,
+    /// [`None`] if the window has no context
+    pub creation_api: Option<ContextCreationApi>,
+    pub major: i32,
+    pub minor: i32,
+    pub revision: i32,
+    pub profile: OpenGlProfile,
+    pub forward_compat: bool,
+    pub debug: bool,
+    pub robustness: ContextRobustness,
+    pub release_behavior: ReleaseBehavior,
+    pub no_error: bool,
+}
+
+impl ClientApi {
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [Self::None, Self::OpenGl, Self::OpenGlEs]
+            .into_iter()
+            .find(|api| *api as i32 == raw)
+    }
+}
+
+impl ContextCreationApi {
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [Self::Native, Self::Egl, Self::OsMesa]
+            .into_iter()
+            .find(|api| *api as i32 == raw)
+    }
+}
+
+impl OpenGlProfile {
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [Self::Any, Self::Core, Self::Compat]
+            .into_iter()
+            .find(|profile| *profile as i32 == raw)
+    }
+}
+
+impl ContextRobustness {
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [
+            Self::None,
+            Self::NoResetNotification,
+            Self::LoseContextOnReset,
+        ]
+        .into_iter()
+        .find(|robustness| *robustness as i32 == raw)
+    }
+}
+
+impl ReleaseBehavior {
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [Self::Any, Self::Flush, Self::None]
+            .into_iter()
+            .find(|behavior| *behavior as i32 == raw)
+    }
+}
@@ -1,67 +1,295 @@
-use crate::{ErrorHandler, Window};
+use crate::{
+    Allocator, ContextCreationApi, ControlFlow, ErrorHandler, Event, EventLoopProxy, GlfwInstant,
+    InitError, NoContext, OffscreenContext, Platform, Window, allocator, event, offscreen, state,
+    time, version,
+};
 use glfw_rs_sys as glfw_sys;
-use std::{marker::PhantomData, sync::OnceLock};
+use std::{
+    ffi::CString,
+    iter,
+    marker::PhantomData,
+    sync::{
+        Mutex, OnceLock, PoisonError,
+        atomic::{AtomicU8, Ordering},
+    },
+    thread::{self, ThreadId},
+    time::{Duration, Instant},
+};
 
 static CONTEXT: OnceLock<Context> = OnceLock::new();
+static INIT_LOCK: Mutex<()> = Mutex::new(());
+/// The depth bits hint, GLFW has no way to read the hints back
+static DEPTH_BITS: AtomicU8 = AtomicU8::new(24);
+
+/// The depth bits the windows created now are requested with
+pub(crate) fn depth_bits_hint() -> u8 {
+    DEPTH_BITS.load(Ordering::Relaxed)
+}
+
+/// Options for [`Context::init_with`]
+#[derive(Default)]
+pub struct InitOptions {
+    allocator: Option<Allocator>,
+    platform: Option<Platform>,
+}
+
+impl InitOptions {
+    /// Make GLFW allocate its memory with `allocator`. Requires GLFW 3.4
+    pub fn allocator(mut self, allocator: Allocator) -> Self {
+        self.allocator = Some(allocator);
+        self
+    }
+
+    /// Make GLFW initialize `platform` instead of picking one, e.g.
+    /// [`Platform::Null`] to run without a display. Initializing fails if the
+    /// library doesn't support it, see [`Context::platform_supported`].
+    /// Requires GLFW 3.4
+    pub fn platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+}
 
 /// The GLFW context, can only be constructed once.
+///
+/// Most GLFW functions must only be called from the main thread, which is the
+/// thread that called [`Context::init`]. The methods which create windows or
+/// process events panic when called from any other thread. Windows can't be
+/// sent to other threads, so they are always used on the main thread.
 pub struct Context {
     hidden: PhantomData<()>,
+    main_thread: ThreadId,
 }
 
 impl Context {
-    /// Initialize the context, or just get it if its already initialized
-    pub fn init() -> &'static Context {
-        CONTEXT.get_or_init(|| {
-            unsafe { glfw_sys::glfwInit() };
-            Self {
-                hidden: PhantomData,
-            }
-        })
+    /// Initialize the context, or just get it if its already initialized. The
+    /// thread which initializes the context becomes the main thread.
+    pub fn init() -> Result<&'static Context, InitError> {
+        Self::init_with(InitOptions::default())
+    }
+
+    /// Initialize the context with `options`, or just get it if its already
+    /// initialized, in which case the options are ignored.
+    pub fn init_with(options: InitOptions) -> Result<&'static Context, InitError> {
+        // keeps two threads from initializing GLFW at the same time
+        let _guard = INIT_LOCK.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(ctx) = CONTEXT.get() {
+            return Ok(ctx);
+        }
+
+        #[cfg(feature = "dlopen")]
+        glfw_sys::load().map_err(InitError::LibraryUnavailable)?;
+
+        if options.allocator.is_some() || options.platform.is_some() {
+            version::require(3, 4).map_err(|_| InitError::FeatureUnavailable)?;
+        }
+        if let Some(allocator) = options.allocator {
+            allocator::install(allocator);
+        }
+        if let Some(platform) = options.platform {
+            unsafe { glfw_sys::glfwInitHint(glfw_sys::GLFW_PLATFORM as i32, platform as i32) };
+        }
+
+        if unsafe { glfw_sys::glfwInit() } != glfw_sys::GLFW_TRUE as i32 {
+            return Err(InitError::Failed);
+        }
+
+        Ok(CONTEXT.get_or_init(|| Self {
+            hidden: PhantomData,
+            main_thread: thread::current().id(),
+        }))
     }
 
     /// Get the context, returns [`None`] if it hasn't been initialized.
     pub fn get() -> Option<&'static Context> {
         CONTEXT.get()
     }
+
+    /// Check if this is the thread which initialized the context
+    pub fn is_main_thread(&self) -> bool {
+        thread::current().id() == self.main_thread
+    }
+
+    fn assert_main_thread(&self) {
+        assert!(
+            self.is_main_thread(),
+            "this GLFW function must only be called from the main thread"
+        );
+    }
 }
 
 impl Context {
     /// Poll the events and run handleres registered in the context.
     pub fn poll_events(&self) {
+        self.assert_main_thread();
+        offscreen::destroy_pending();
+        event::discard_window_events();
         unsafe { glfw_sys::glfwPollEvents() }
     }
 
     /// Wait for events to happen
     pub fn wait_events(&self) {
+        self.assert_main_thread();
+        offscreen::destroy_pending();
+        event::discard_window_events();
         unsafe { glfw_sys::glfwWaitEvents() }
     }
 
-    /// Set the amount of frames to wait to rerender
+    /// Wait for events to happen, but no longer than `timeout`
+    pub fn wait_events_timeout(&self, timeout: Duration) {
+        self.assert_main_thread();
+        if timeout.is_zero() {
+            return self.poll_events();
+        }
+
+        offscreen::destroy_pending();
+        event::discard_window_events();
+        unsafe { glfw_sys::glfwWaitEventsTimeout(timeout.as_secs_f64()) }
+    }
+
+    /// Wait for events to happen, but no longer than until `deadline`
+    pub fn wait_events_until(&self, deadline: Instant) {
+        self.wait_events_timeout(deadline.saturating_duration_since(Instant::now()))
+    }
+
+    /// Take the events which happened since the last time the events were polled.
+    /// Window events which weren't taken are discarded the next time the events
+    /// are polled, user events stay in the queue until they are taken.
+    pub fn events(&self) -> impl Iterator<Item = Event> + '_ {
+        iter::from_fn(event::pop_event)
+    }
+
+    /// Run the event loop, calling `handler` for every event, then with
+    /// [`Event::AboutToWait`] before waiting for the next ones. The loop stops when
+    /// the handler sets the control flow to [`ControlFlow::Exit`], or when there
+    /// are windows and all of them should close. For more control, call [`Context::poll_events`] and
+    /// [`Context::events`] in your own loop instead.
+    pub fn run<F: FnMut(Event, &mut ControlFlow)>(&self, mut handler: F) {
+        self.assert_main_thread();
+        let mut control = ControlFlow::default();
+
+        loop {
+            match control {
+                ControlFlow::Exit => break,
+                // redraws requested while handling the last events would only be
+                // emitted after the next event arrives, so don't wait for it
+                _ if state::has_redraw_requests() => self.poll_events(),
+                ControlFlow::Poll => self.poll_events(),
+                ControlFlow::Wait => self.wait_events(),
+                ControlFlow::WaitUntil(deadline) => self.wait_events_until(deadline),
+            }
+
+            // waiting for a deadline which passed would return right away, so the
+            // loop would spin until the handler picks another control flow
+            if let ControlFlow::WaitUntil(deadline) = control
+                && Instant::now() >= deadline
+            {
+                control = ControlFlow::Wait;
+                handler(Event::ResumeTimeReached, &mut control);
+            }
+
+            for event in self.events() {
+                handler(event, &mut control);
+            }
+
+            for window in state::take_redraw_requests() {
+                handler(Event::RedrawRequested { window }, &mut control);
+            }
+
+            handler(Event::AboutToWait, &mut control);
+            if control == ControlFlow::Exit || state::all_windows_should_close() {
+                break;
+            }
+        }
+    }
+
+    /// Create a proxy which other threads can use to wake up the event loop and
+    /// send it events of type `T`
+    pub fn create_proxy<T: Send + 'static>(&self) -> EventLoopProxy<T> {
+        EventLoopProxy::new()
+    }
+
+    /// Set the amount of frames to wait to rerender. This applies to the context
+    /// which is current on this thread, see [`Window::set_swap_interval`]
     pub fn set_swap_interval(&self, interval: i32) {
         unsafe { glfw_sys::glfwSwapInterval(interval) }
     }
 
+    /// Check if an OpenGL or OpenGL ES extension is supported by the context which
+    /// is current on this thread
+    pub fn extension_supported(&self, extension: &str) -> bool {
+        let Ok(extension) = CString::new(extension) else {
+            return false;
+        };
+        unsafe { glfw_sys::glfwExtensionSupported(extension.as_ptr()) == 1 }
+    }
+
     /// Set the error handler for the current context. You can use [`parse_params`]
     /// to parse the arguments from [`ErrorHandler`]
     ///
     /// [parse_params]: crate::parse_params
     pub fn set_handler(&self, handler: ErrorHandler) {
+        self.assert_main_thread();
         unsafe { glfw_sys::glfwSetErrorCallback(Some(handler)) };
     }
 
     /// Create a window inside the current context
     pub fn window<S: AsRef<str>>(&self, name: S, size: (i32, i32)) -> Option<Window> {
+        self.assert_main_thread();
         Window::new(name, size)
     }
 
+    /// Create a window without an OpenGL context, for use with Vulkan or wgpu. The
+    /// returned window doesn't have the methods which need a context
+    pub fn window_no_api<S: AsRef<str>>(
+        &self,
+        name: S,
+        size: (i32, i32),
+    ) -> Option<Window<NoContext>> {
+        self.assert_main_thread();
+        unsafe {
+            glfw_sys::glfwWindowHint(
+                glfw_sys::GLFW_CLIENT_API as i32,
+                glfw_sys::GLFW_NO_API as i32,
+            );
+            let window = Window::new(name, size);
+            glfw_sys::glfwWindowHint(
+                glfw_sys::GLFW_CLIENT_API as i32,
+                glfw_sys::GLFW_OPENGL_API as i32,
+            );
+            window
+        }
+    }
+
+    /// Create a window whose OpenGL context shares its objects, like textures and
+    /// buffers, with the context of `shared`
+    pub fn window_shared<S: AsRef<str>>(
+        &self,
+        name: S,
+        size: (i32, i32),
+        shared: &Window,
+    ) -> Option<Window> {
+        self.assert_main_thread();
+        Window::new_shared(name, size, shared)
+    }
+
+    /// Create an OpenGL context without a visible window, which shares its objects
+    /// with the context of `shared`. It can be sent to another thread to load
+    /// resources in the background. Resets the visibility window hint.
+    pub fn offscreen_context(&self, shared: &Window) -> Option<OffscreenContext> {
+        self.assert_main_thread();
+        OffscreenContext::new(shared)
+    }
+
     /// Create a fullscreen window inside the current context
     pub fn window_fullscreen<S: AsRef<str>>(&self, name: S) -> Option<Window> {
+        self.assert_main_thread();
         Window::new_fullscreen(name)
     }
 
     /// Set the version of the OpenGL context. Should be called before creating a window.
     pub fn gl_version(&self, major: i32, minor: i32) {
+        self.assert_main_thread();
         unsafe {
             glfw_sys::glfwWindowHint(glfw_sys::GLFW_CONTEXT_VERSION_MAJOR as i32, major);
             glfw_sys::glfwWindowHint(glfw_sys::GLFW_CONTEXT_VERSION_MINOR as i32, minor);
@@ -71,6 +299,34 @@ impl Context {
             );
         }
     }
+
+    /// Set the API used to create the context of the windows created after this
+    /// call. [`ContextCreationApi::OsMesa`] renders in software, without a GPU.
+    pub fn context_creation_api(&self, api: ContextCreationApi) {
+        self.assert_main_thread();
+        unsafe { glfw_sys::glfwWindowHint(glfw_sys::GLFW_CONTEXT_CREATION_API as i32, api as i32) };
+    }
+
+    /// Set the amount of bits in the depth buffer of the windows created after
+    /// this call, 24 by default
+    pub fn depth_bits(&self, bits: u8) {
+        self.assert_main_thread();
+        DEPTH_BITS.store(bits, Ordering::Relaxed);
+        unsafe { glfw_sys::glfwWindowHint(glfw_sys::GLFW_DEPTH_BITS as i32, bits as i32) };
+    }
+
+    /// Request a framebuffer with per-pixel transparency for the windows created
+    /// after this call. Use [`Window::has_transparent_framebuffer`] to check if the
+    /// platform supports it.
+    pub fn transparent_framebuffer(&self, enabled: bool) {
+        self.assert_main_thread();
+        unsafe {
+            glfw_sys::glfwWindowHint(
+                glfw_sys::GLFW_TRANSPARENT_FRAMEBUFFER as i32,
+                enabled as i32,
+            )
+        };
+    }
 }
 
 impl Drop for Context {
@@ -84,7 +340,19 @@ impl Context {
         unsafe { glfw_sys::glfwGetTime() }
     }
 
-    pub fn set_time(&self, time: f64) {
-        unsafe { glfw_sys::glfwSetTime(time) }
+    /// Set the time returned by [`Context::get_time`]
+    pub fn set_time(&self, time: Duration) {
+        unsafe { glfw_sys::glfwSetTime(time.as_secs_f64()) }
+    }
+
+    /// Get the current value of the raw timer. Prefer this over [`Context::get_time`]
+    /// for measuring time, since it doesn't lose precision
+    pub fn now(&self) -> GlfwInstant {
+        GlfwInstant::now()
+    }
+
+    /// The frequency of the raw timer, in ticks per second
+    pub fn timer_frequency(&self) -> u64 {
+        time::frequency()
     }
 }
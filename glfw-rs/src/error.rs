@@ -14,6 +14,25 @@ pub enum Error {
     VersionUnavailable = glfw_sys::GLFW_VERSION_UNAVAILABLE as i32,
     PlatformError = glfw_sys::GLFW_PLATFORM_ERROR as i32,
     FormatUnavailable = glfw_sys::GLFW_FORMAT_UNAVAILABLE as i32,
+    /// The feature isn't available on this platform, or needs a newer GLFW
+    /// library than the one being used
+    FeatureUnavailable = glfw_sys::GLFW_FEATURE_UNAVAILABLE as i32,
+}
+
+/// The reasons [`Context::init`] can fail, also returned by the queries which can
+/// be made before it, like [`Context::version`]
+///
+/// [`Context::init`]: crate::Context::init
+/// [`Context::version`]: crate::Context::version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InitError {
+    /// The GLFW library couldn't be loaded at runtime, only with the `dlopen`
+    /// feature
+    LibraryUnavailable(String),
+    /// An option needs a newer GLFW library than the one being used
+    FeatureUnavailable,
+    /// `glfwInit` failed, e.g. because there's no display to connect to
+    Failed,
 }
 
 pub type ErrorHandler = unsafe extern "C" fn(i32, *const i8);
@@ -0,0 +1,254 @@
+use crate::state;
+use glfw_rs_sys as glfw_sys;
+use glfw_sys::GLFWwindow;
+use std::{
+    any::Any,
+    collections::VecDeque,
+    fmt,
+    marker::PhantomData,
+    sync::{Mutex, MutexGuard},
+    time::Instant,
+};
+
+static EVENTS: Mutex<VecDeque<Event>> = Mutex::new(VecDeque::new());
+
+/// An identifier of a window, which stays the same for the lifetime of the window.
+/// Can be obtained with [`Window::id`]
+///
+/// [`Window::id`]: crate::Window::id
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WindowId(usize);
+
+impl WindowId {
+    pub(crate) fn from_raw(raw: *mut GLFWwindow) -> Self {
+        Self(raw as usize)
+    }
+}
+
+/// An event taken from the event queue with [`Context::events`]
+///
+/// [`Context::events`]: crate::Context::events
+#[derive(Debug)]
+pub enum Event {
+    /// The user tried to close the window and the close handler didn't prevent it
+    CloseRequested {
+        window: WindowId,
+    },
+    /// The window was resized, the size is in screen coordinates
+    Resized {
+        window: WindowId,
+        width: i32,
+        height: i32,
+    },
+    /// The framebuffer of the window was resized, the size is in pixels
+    FramebufferResized {
+        window: WindowId,
+        width: i32,
+        height: i32,
+    },
+    /// The window was moved, the position is in screen coordinates
+    Moved {
+        window: WindowId,
+        x: i32,
+        y: i32,
+    },
+    Focused {
+        window: WindowId,
+        focused: bool,
+    },
+    Iconified {
+        window: WindowId,
+        iconified: bool,
+    },
+    Maximized {
+        window: WindowId,
+        maximized: bool,
+    },
+    /// The contents of the window need to be redrawn
+    Refresh {
+        window: WindowId,
+    },
+    /// The window should be redrawn, emitted by [`Context::run`] after
+    /// [`Window::request_redraw`] is called
+    ///
+    /// [`Context::run`]: crate::Context::run
+    /// [`Window::request_redraw`]: crate::Window::request_redraw
+    RedrawRequested {
+        window: WindowId,
+    },
+    /// The deadline of [`ControlFlow::WaitUntil`] was reached, the control flow
+    /// is reset to [`ControlFlow::Wait`] before this event is emitted
+    ResumeTimeReached,
+    /// Emitted by [`Context::run`] once per iteration, after the other events
+    /// were handled and before waiting for new ones
+    ///
+    /// [`Context::run`]: crate::Context::run
+    AboutToWait,
+    /// A custom event sent with an [`EventLoopProxy`]. Use [`Box::downcast`] to get
+    /// the value back
+    User(Box<dyn Any + Send>),
+}
+
+/// Controls how [`Context::run`] waits for the next events
+///
+/// [`Context::run`]: crate::Context::run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ControlFlow {
+    /// Poll the events and run the loop again immediately, useful for games
+    #[default]
+    Poll,
+    /// Sleep until new events arrive
+    Wait,
+    /// Sleep until new events arrive or the deadline is reached. Once it's
+    /// reached, [`Event::ResumeTimeReached`] is emitted
+    WaitUntil(Instant),
+    /// Stop the loop after the current events are handled
+    Exit,
+}
+
+/// A handle which can be used from any thread to wake up the thread waiting for
+/// events and to send it custom events. Created with [`Context::create_proxy`]
+///
+/// [`Context::create_proxy`]: crate::Context::create_proxy
+pub struct EventLoopProxy<T> {
+    marker: PhantomData<fn(T)>,
+}
+
+impl<T: Send + 'static> EventLoopProxy<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+
+    /// Push an [`Event::User`] to the event queue and wake up the event loop
+    pub fn send_event(&self, event: T) {
+        push_event(Event::User(Box::new(event)));
+        self.wake_up();
+    }
+
+    /// Wake up the thread waiting in [`Context::wait_events`] without sending an event
+    ///
+    /// [`Context::wait_events`]: crate::Context::wait_events
+    pub fn wake_up(&self) {
+        unsafe { glfw_sys::glfwPostEmptyEvent() }
+    }
+}
+
+impl<T> Clone for EventLoopProxy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for EventLoopProxy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventLoopProxy").finish_non_exhaustive()
+    }
+}
+
+fn queue() -> MutexGuard<'static, VecDeque<Event>> {
+    // the queue is never left in an invalid state, so ignore the poisoning
+    EVENTS.lock().unwrap_or_else(|err| err.into_inner())
+}
+
+pub(crate) fn push_event(event: Event) {
+    queue().push_back(event);
+}
+
+pub(crate) fn pop_event() -> Option<Event> {
+    queue().pop_front()
+}
+
+/// Drop the window events which weren't taken since the last time the events
+/// were polled. User events are kept until they are taken.
+pub(crate) fn discard_window_events() {
+    queue().retain(|event| matches!(event, Event::User(_)));
+}
+
+/// Install the callbacks which feed the event queue.
+pub(crate) fn install_callbacks(raw: *mut GLFWwindow) {
+    unsafe {
+        glfw_sys::glfwSetWindowCloseCallback(raw, Some(close_callback));
+        glfw_sys::glfwSetWindowSizeCallback(raw, Some(size_callback));
+        glfw_sys::glfwSetFramebufferSizeCallback(raw, Some(framebuffer_size_callback));
+        glfw_sys::glfwSetWindowPosCallback(raw, Some(pos_callback));
+        glfw_sys::glfwSetWindowFocusCallback(raw, Some(focus_callback));
+        glfw_sys::glfwSetWindowIconifyCallback(raw, Some(iconify_callback));
+        glfw_sys::glfwSetWindowMaximizeCallback(raw, Some(maximize_callback));
+        glfw_sys::glfwSetWindowRefreshCallback(raw, Some(refresh_callback));
+    }
+}
+
+unsafe extern "C" fn close_callback(raw: *mut GLFWwindow) {
+    // the handler is taken out of the state while it runs, so it can freely use
+    // the window, including replacing the handler
+    if let Some(mut handler) = state::with_state(raw, |state| state.close_handler.take()) {
+        if !handler(raw) {
+            unsafe { glfw_sys::glfwSetWindowShouldClose(raw, glfw_sys::GLFW_FALSE as i32) };
+        }
+
+        state::with_state(raw, |state| {
+            state.close_handler.get_or_insert(handler);
+        });
+    }
+
+    if unsafe { glfw_sys::glfwWindowShouldClose(raw) } == 1 {
+        push_event(Event::CloseRequested {
+            window: WindowId::from_raw(raw),
+        });
+    }
+}
+
+unsafe extern "C" fn size_callback(raw: *mut GLFWwindow, width: i32, height: i32) {
+    push_event(Event::Resized {
+        window: WindowId::from_raw(raw),
+        width,
+        height,
+    });
+}
+
+unsafe extern "C" fn framebuffer_size_callback(raw: *mut GLFWwindow, width: i32, height: i32) {
+    push_event(Event::FramebufferResized {
+        window: WindowId::from_raw(raw),
+        width,
+        height,
+    });
+}
+
+unsafe extern "C" fn pos_callback(raw: *mut GLFWwindow, x: i32, y: i32) {
+    push_event(Event::Moved {
+        window: WindowId::from_raw(raw),
+        x,
+        y,
+    });
+}
+
+unsafe extern "C" fn focus_callback(raw: *mut GLFWwindow, focused: i32) {
+    push_event(Event::Focused {
+        window: WindowId::from_raw(raw),
+        focused: focused == 1,
+    });
+}
+
+unsafe extern "C" fn iconify_callback(raw: *mut GLFWwindow, iconified: i32) {
+    push_event(Event::Iconified {
+        window: WindowId::from_raw(raw),
+        iconified: iconified == 1,
+    });
+}
+
+unsafe extern "C" fn maximize_callback(raw: *mut GLFWwindow, maximized: i32) {
+    push_event(Event::Maximized {
+        window: WindowId::from_raw(raw),
+        maximized: maximized == 1,
+    });
+}
+
+unsafe extern "C" fn refresh_callback(raw: *mut GLFWwindow) {
+    push_event(Event::Refresh {
+        window: WindowId::from_raw(raw),
+    });
+}
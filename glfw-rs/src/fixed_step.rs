@@ -0,0 +1,195 @@
+use crate::{Context, GlfwInstant};
+use std::time::Duration;
+
+/// Statistics collected by a [`FixedStepLoop`]. All times are in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FixedStepStats {
+    /// The amount of frames rendered
+    pub frames: u64,
+    /// The amount of fixed updates ran
+    pub updates: u64,
+    /// The amount of fixed updates ran in the last frame
+    pub updates_last_frame: u32,
+    /// The time between the last two frames, before clamping
+    pub frame_time: f64,
+    /// The total time elapsed since the first frame
+    pub elapsed: f64,
+    /// The total time skipped because frames took longer than the maximum frame time
+    pub dropped_time: f64,
+}
+
+impl FixedStepStats {
+    /// The average time between frames since the first frame
+    pub fn average_frame_time(&self) -> f64 {
+        if self.frames <= 1 {
+            return 0.0;
+        }
+
+        self.elapsed / (self.frames - 1) as f64
+    }
+
+    /// The average frames per second since the first frame
+    pub fn fps(&self) -> f64 {
+        match self.average_frame_time() {
+            0.0 => 0.0,
+            frame_time => 1.0 / frame_time,
+        }
+    }
+}
+
+/// A game loop helper which runs the simulation at a fixed rate, independent of the
+/// frame rate, and renders with an interpolation factor between the last two
+/// simulation states. Call [`FixedStepLoop::frame`] once per frame.
+///
+/// ```no_run
+/// # use glfw_rs::{Context, FixedStepLoop};
+/// # use std::time::Duration;
+/// let ctx = Context::init().expect("Cannot initialize GLFW!");
+/// let window = ctx.window("Game", (640, 480)).unwrap();
+/// let mut game_loop = FixedStepLoop::new(Duration::from_secs(1) / 60);
+///
+/// while !window.should_close() {
+///     game_loop.frame(ctx, |dt| { /* update the simulation */ }, |alpha| { /* render */ });
+///     window.update();
+///     ctx.poll_events();
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct FixedStepLoop {
+    step: f64,
+    max_frame_time: f64,
+    accumulator: f64,
+    last_time: Option<GlfwInstant>,
+    stats: FixedStepStats,
+}
+
+impl FixedStepLoop {
+    /// The default maximum frame time, see [`FixedStepLoop::set_max_frame_time`]
+    pub const DEFAULT_MAX_FRAME_TIME: Duration = Duration::from_millis(250);
+
+    /// Create a loop which runs an update every `step`
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "The fixed step must not be zero");
+        Self {
+            step: step.as_secs_f64(),
+            max_frame_time: Self::DEFAULT_MAX_FRAME_TIME.as_secs_f64(),
+            accumulator: 0.0,
+            last_time: None,
+            stats: FixedStepStats::default(),
+        }
+    }
+
+    /// Set the longest frame time which is simulated. If a frame takes longer, for
+    /// example because the window was being dragged, the rest of the time is
+    /// dropped instead of running more and more updates to catch up.
+    pub fn set_max_frame_time(&mut self, max_frame_time: Duration) {
+        self.max_frame_time = max_frame_time.as_secs_f64();
+    }
+
+    /// The time between two updates
+    pub fn step(&self) -> Duration {
+        Duration::from_secs_f64(self.step)
+    }
+
+    pub fn stats(&self) -> &FixedStepStats {
+        &self.stats
+    }
+
+    /// Reset the accumulated time, e.g. after the game was paused. The next frame
+    /// doesn't run any updates.
+    pub fn reset(&mut self) {
+        self.accumulator = 0.0;
+        self.last_time = None;
+    }
+
+    /// Run as many `update`s as the elapsed time requires, then `render` once.
+    /// `update` receives the step in seconds, and `render` receives the
+    /// interpolation factor between the previous and the current simulation
+    /// state, from 0.0 to 1.0.
+    pub fn frame<U: FnMut(f64), R: FnMut(f64)>(&mut self, ctx: &Context, update: U, render: R) {
+        let now = ctx.now();
+        let delta = match self.last_time.replace(now) {
+            Some(last) => now - last,
+            None => Duration::ZERO,
+        };
+        self.frame_with_delta(delta, update, render);
+    }
+
+    /// Like [`FixedStepLoop::frame`], but with the time since the last frame
+    /// given instead of measured, e.g. to replay recorded frame times
+    pub fn frame_with_delta<U: FnMut(f64), R: FnMut(f64)>(
+        &mut self,
+        delta: Duration,
+        mut update: U,
+        mut render: R,
+    ) {
+        let frame_time = delta.as_secs_f64();
+        self.stats.frame_time = frame_time;
+        self.stats.elapsed += frame_time;
+        self.stats.dropped_time += (frame_time - self.max_frame_time).max(0.0);
+        self.accumulator += frame_time.min(self.max_frame_time);
+
+        let mut updates = 0;
+        while self.accumulator >= self.step {
+            update(self.step);
+            self.accumulator -= self.step;
+            updates += 1;
+        }
+
+        self.stats.updates += updates as u64;
+        self.stats.updates_last_frame = updates;
+        self.stats.frames += 1;
+
+        render(self.accumulator / self.step);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a power of two fraction of a second, so the accumulated time is exact
+    const STEP: Duration = Duration::from_nanos(1_000_000_000 / 64);
+
+    #[test]
+    fn long_frames_are_clamped() {
+        let mut game_loop = FixedStepLoop::new(STEP);
+        let mut updates = 0;
+        game_loop.frame_with_delta(Duration::from_secs(1), |_| updates += 1, |_| {});
+
+        // 250 ms are simulated, the other 750 ms are dropped
+        assert_eq!(updates, 16);
+        assert_eq!(game_loop.stats().updates_last_frame, 16);
+        assert_eq!(game_loop.stats().dropped_time, 0.75);
+        assert_eq!(game_loop.stats().elapsed, 1.0);
+    }
+
+    #[test]
+    fn updates_last_frame_counts_only_the_last_frame() {
+        let mut game_loop = FixedStepLoop::new(STEP);
+        game_loop.frame_with_delta(STEP * 3 + STEP / 2, |_| {}, |_| {});
+        assert_eq!(game_loop.stats().updates_last_frame, 3);
+
+        // the leftover half step is carried over
+        game_loop.frame_with_delta(STEP / 2, |_| {}, |_| {});
+        assert_eq!(game_loop.stats().updates_last_frame, 1);
+
+        game_loop.frame_with_delta(STEP / 4, |_| {}, |_| {});
+        assert_eq!(game_loop.stats().updates_last_frame, 0);
+        assert_eq!(game_loop.stats().updates, 4);
+        assert_eq!(game_loop.stats().frames, 3);
+    }
+
+    #[test]
+    fn alpha_stays_below_one() {
+        let mut game_loop = FixedStepLoop::new(STEP);
+        for millis in [0, 1, 7, 15, 16, 17, 33, 100, 400] {
+            let mut alpha = f64::NAN;
+            game_loop.frame_with_delta(Duration::from_millis(millis), |_| {}, |a| alpha = a);
+            assert!(
+                (0.0..1.0).contains(&alpha),
+                "alpha {alpha} after {millis} ms"
+            );
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::GlfwInstant;
+use std::{collections::VecDeque, fmt, time::Duration};
+
+/// Frame timing statistics, collected from the buffer swaps of a window. Enable
+/// them with [`Window::enable_frame_stats`] and read them with
+/// [`Window::frame_stats`].
+///
+/// The frame times are kept for a limited amount of recent frames, so the
+/// averages and percentiles describe the recent frame pacing, not the whole run.
+///
+/// [`Window::enable_frame_stats`]: crate::Window::enable_frame_stats
+/// [`Window::frame_stats`]: crate::Window::frame_stats
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    frame_times: VecDeque<Duration>,
+    capacity: usize,
+    last_swap: Option<GlfwInstant>,
+    refresh_interval: Option<Duration>,
+    swap_interval: u32,
+    frames: u64,
+    missed_vsyncs: u64,
+    log_interval: Option<u64>,
+}
+
+impl FrameStats {
+    /// Create a collector which keeps the times of the last `capacity` frames
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            frame_times: VecDeque::with_capacity(capacity),
+            capacity,
+            last_swap: None,
+            refresh_interval: None,
+            swap_interval: 1,
+            frames: 0,
+            missed_vsyncs: 0,
+            log_interval: None,
+        }
+    }
+
+    /// Set the refresh rate of the monitor, in Hz, used to detect missed vsync
+    /// intervals. A rate of zero disables the detection
+    pub fn set_refresh_rate(&mut self, refresh_rate: u32) {
+        self.refresh_interval = match refresh_rate {
+            0 => None,
+            rate => Some(Duration::from_secs(1) / rate),
+        };
+    }
+
+    /// Set the swap interval the buffers are swapped with, a frame is expected to
+    /// take that many refresh intervals. An interval of zero disables the
+    /// detection of missed vsync intervals. [`Window::set_swap_interval`] sets it
+    /// for the statistics of the window
+    ///
+    /// [`Window::set_swap_interval`]: crate::Window::set_swap_interval
+    pub fn set_swap_interval(&mut self, interval: i32) {
+        // negative intervals allow tearing when late, but wait the same otherwise
+        self.swap_interval = interval.unsigned_abs();
+    }
+
+    /// Print the statistics to stderr every `frames` frames, or never if [`None`]
+    pub fn set_log_interval(&mut self, frames: Option<u64>) {
+        self.log_interval = frames.filter(|frames| *frames > 0);
+    }
+
+    /// Record a buffer swap which happened at `now`. The first swap only counts
+    /// as a frame, the following ones record the time since the previous swap
+    pub(crate) fn record_swap(&mut self, now: GlfwInstant) {
+        match self.last_swap.replace(now) {
+            Some(last) => self.record_frame(now - last),
+            None => self.frames += 1,
+        }
+    }
+
+    /// Record a frame which took `frame_time`
+    pub fn record_frame(&mut self, frame_time: Duration) {
+        self.frames += 1;
+        if self.frame_times.len() == self.capacity {
+            self.frame_times.pop_front();
+        }
+        self.frame_times.push_back(frame_time);
+
+        if let Some(interval) = self.refresh_interval.filter(|_| self.swap_interval > 0) {
+            // a frame which took a bit longer than it should have still made it
+            // in time, so round to the closest amount of intervals
+            let intervals = (frame_time.as_secs_f64() / interval.as_secs_f64()).round() as u64;
+            self.missed_vsyncs += intervals.saturating_sub(self.swap_interval as u64);
+        }
+
+        if self
+            .log_interval
+            .is_some_and(|interval| self.frames.is_multiple_of(interval))
+        {
+            eprintln!("{self}");
+        }
+    }
+
+    /// The total amount of frames recorded
+    pub fn frames(&self) -> u64 {
+        self.frames
+    }
+
+    /// The total amount of vsync intervals missed, based on the refresh rate and
+    /// the swap interval
+    pub fn missed_vsyncs(&self) -> u64 {
+        self.missed_vsyncs
+    }
+
+    /// The times of the recent frames, from the oldest to the newest
+    pub fn frame_times(&self) -> impl Iterator<Item = Duration> + '_ {
+        self.frame_times.iter().copied()
+    }
+
+    /// The time between the last two swaps
+    pub fn last(&self) -> Option<Duration> {
+        self.frame_times.back().copied()
+    }
+
+    pub fn average(&self) -> Option<Duration> {
+        let len = u32::try_from(self.frame_times.len())
+            .ok()
+            .filter(|len| *len > 0)?;
+        let total: Duration = self.frame_times.iter().sum();
+        Some(total / len)
+    }
+
+    pub fn min(&self) -> Option<Duration> {
+        self.frame_times.iter().min().copied()
+    }
+
+    pub fn max(&self) -> Option<Duration> {
+        self.frame_times.iter().max().copied()
+    }
+
+    /// The frame time below which `percentile` percent of the recent frames are,
+    /// e.g. `percentile(99.0)` for the 99th percentile
+    pub fn percentile(&self, percentile: f64) -> Option<Duration> {
+        if self.frame_times.is_empty() {
+            return None;
+        }
+
+        let mut sorted: Vec<_> = self.frame_times.iter().copied().collect();
+        sorted.sort_unstable();
+
+        let rank = (percentile.clamp(0.0, 100.0) / 100.0 * sorted.len() as f64).ceil() as usize;
+        Some(sorted[rank.saturating_sub(1)])
+    }
+
+    /// The average frames per second of the recent frames
+    pub fn fps(&self) -> Option<f64> {
+        let average = self.average()?.as_secs_f64();
+        (average > 0.0).then(|| 1.0 / average)
+    }
+}
+
+impl fmt::Display for FrameStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ms = |time: Option<Duration>| time.unwrap_or_default().as_secs_f64() * 1000.0;
+
+        write!(
+            f,
+            "frame {}: {:.1} fps, avg {:.2} ms, min {:.2} ms, max {:.2} ms, p99 {:.2} ms, {} missed vsyncs",
+            self.frames,
+            self.fps().unwrap_or_default(),
+            ms(self.average()),
+            ms(self.min()),
+            ms(self.max()),
+            ms(self.percentile(99.0)),
+            self.missed_vsyncs,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats_with(millis: &[u64]) -> FrameStats {
+        let mut stats = FrameStats::new(millis.len());
+        for &time in millis {
+            stats.record_frame(Duration::from_millis(time));
+        }
+        stats
+    }
+
+    #[test]
+    fn min_max_and_average() {
+        let stats = stats_with(&[16, 17, 15, 20]);
+        assert_eq!(stats.min(), Some(Duration::from_millis(15)));
+        assert_eq!(stats.max(), Some(Duration::from_millis(20)));
+        assert_eq!(stats.average(), Some(Duration::from_millis(17)));
+        assert_eq!(stats.last(), Some(Duration::from_millis(20)));
+        assert_eq!(stats.frames(), 4);
+
+        let empty = FrameStats::new(4);
+        assert_eq!(
+            (empty.min(), empty.max(), empty.average()),
+            (None, None, None)
+        );
+    }
+
+    #[test]
+    fn percentile() {
+        let stats = stats_with(&(1..=100).rev().collect::<Vec<_>>());
+        assert_eq!(stats.percentile(50.0), Some(Duration::from_millis(50)));
+        assert_eq!(stats.percentile(99.0), Some(Duration::from_millis(99)));
+        assert_eq!(stats.percentile(100.0), Some(Duration::from_millis(100)));
+        assert_eq!(stats.percentile(0.0), Some(Duration::from_millis(1)));
+        assert_eq!(FrameStats::new(1).percentile(50.0), None);
+    }
+
+    #[test]
+    fn only_the_recent_frames_are_kept() {
+        let mut stats = stats_with(&[100, 1, 2]);
+        stats.record_frame(Duration::from_millis(3));
+        assert_eq!(stats.max(), Some(Duration::from_millis(3)));
+        assert_eq!(stats.frame_times().count(), 3);
+        assert_eq!(stats.frames(), 4);
+    }
+
+    #[test]
+    fn missed_vsyncs() {
+        let mut stats = FrameStats::new(8);
+        stats.set_refresh_rate(50);
+        // a bit late still counts as on time, two intervals miss one
+        for millis in [20, 21, 40, 60] {
+            stats.record_frame(Duration::from_millis(millis));
+        }
+        assert_eq!(stats.missed_vsyncs(), 3);
+    }
+
+    #[test]
+    fn missed_vsyncs_scale_with_swap_interval() {
+        let mut stats = FrameStats::new(8);
+        stats.set_refresh_rate(50);
+        stats.set_swap_interval(2);
+        for millis in [40, 41, 60, 80] {
+            stats.record_frame(Duration::from_millis(millis));
+        }
+        assert_eq!(stats.missed_vsyncs(), 3);
+
+        // without vsync nothing can be missed
+        stats.set_swap_interval(0);
+        stats.record_frame(Duration::from_millis(100));
+        assert_eq!(stats.missed_vsyncs(), 3);
+    }
+}
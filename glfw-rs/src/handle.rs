@@ -0,0 +1,78 @@
+//! The X11 and Wayland handles are available on Unix other than macOS, the
+//! `raw-window-handle` feature enables both backends. Everything else is
+//! [`HandleError::NotSupported`].
+
+#[cfg(all(unix, not(target_os = "macos"), feature = "wayland"))]
+use raw_window_handle::{WaylandDisplayHandle, WaylandWindowHandle};
+#[cfg(all(unix, not(target_os = "macos"), feature = "x11"))]
+use raw_window_handle::{XlibDisplayHandle, XlibWindowHandle};
+#[cfg(all(
+    unix,
+    not(target_os = "macos"),
+    any(feature = "x11", feature = "wayland")
+))]
+use {crate::Platform, glfw_rs_sys as glfw_sys, std::ptr::NonNull};
+
+use crate::{Context, Window, version::platform};
+use raw_window_handle::{
+    DisplayHandle, HandleError, HasDisplayHandle, HasWindowHandle, RawDisplayHandle,
+    RawWindowHandle, WindowHandle,
+};
+
+fn raw_display_handle() -> Result<RawDisplayHandle, HandleError> {
+    match platform() {
+        #[cfg(all(unix, not(target_os = "macos"), feature = "x11"))]
+        Ok(Platform::X11) => {
+            let display = unsafe { glfw_sys::glfwGetX11Display() };
+            // GLFW uses the default screen of the display, which isn't exposed
+            // without linking to Xlib
+            let handle = XlibDisplayHandle::new(NonNull::new(display.cast()), 0);
+            Ok(handle.into())
+        }
+        #[cfg(all(unix, not(target_os = "macos"), feature = "wayland"))]
+        Ok(Platform::Wayland) => {
+            let display = unsafe { glfw_sys::glfwGetWaylandDisplay() };
+            let display = NonNull::new(display.cast()).ok_or(HandleError::Unavailable)?;
+            Ok(WaylandDisplayHandle::new(display).into())
+        }
+        _ => Err(HandleError::NotSupported),
+    }
+}
+
+impl<C> HasWindowHandle for Window<C> {
+    fn window_handle(&self) -> Result<WindowHandle<'_>, HandleError> {
+        let raw: RawWindowHandle = match platform() {
+            #[cfg(all(unix, not(target_os = "macos"), feature = "x11"))]
+            Ok(Platform::X11) => {
+                let window = unsafe { glfw_sys::glfwGetX11Window(self.raw()) };
+                if window == 0 {
+                    return Err(HandleError::Unavailable);
+                }
+                Ok(XlibWindowHandle::new(window).into())
+            }
+            #[cfg(all(unix, not(target_os = "macos"), feature = "wayland"))]
+            Ok(Platform::Wayland) => {
+                let surface = unsafe { glfw_sys::glfwGetWaylandWindow(self.raw()) };
+                let surface = NonNull::new(surface.cast()).ok_or(HandleError::Unavailable)?;
+                Ok(WaylandWindowHandle::new(surface).into())
+            }
+            _ => Err(HandleError::NotSupported),
+        }?;
+
+        // the native window lives as long as the GLFW window
+        Ok(unsafe { WindowHandle::borrow_raw(raw) })
+    }
+}
+
+impl<C> HasDisplayHandle for Window<C> {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        // the display is kept open until GLFW is terminated
+        raw_display_handle().map(|raw| unsafe { DisplayHandle::borrow_raw(raw) })
+    }
+}
+
+impl HasDisplayHandle for Context {
+    fn display_handle(&self) -> Result<DisplayHandle<'_>, HandleError> {
+        raw_display_handle().map(|raw| unsafe { DisplayHandle::borrow_raw(raw) })
+    }
+}
@@ -0,0 +1,71 @@
+use glfw_rs_sys as glfw_sys;
+use glfw_sys::GLFWimage;
+
+/// An RGBA image which can be used as a window icon, see [`Window::set_icon`].
+///
+/// [`Window::set_icon`]: crate::Window::set_icon
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IconImage {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl IconImage {
+    /// Create an icon from 8-bit RGBA pixels, arranged left-to-right, top-to-bottom.
+    /// Returns [`None`] if the amount of pixels doesn't match the size
+    pub fn new(width: u32, height: u32, pixels: Vec<u8>) -> Option<Self> {
+        let len = (width as usize)
+            .checked_mul(height as usize)?
+            .checked_mul(4)?;
+
+        if width == 0 || height == 0 || pixels.len() != len {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    /// Load an icon from an image file, e.g. a PNG
+    #[cfg(feature = "image")]
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> image::ImageResult<Self> {
+        let image = image::open(path)?;
+        Ok(image.to_rgba8().into())
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    /// The returned image borrows the pixels of `self`, GLFW never writes to them.
+    pub(crate) fn as_raw(&self) -> GLFWimage {
+        GLFWimage {
+            width: self.width as i32,
+            height: self.height as i32,
+            pixels: self.pixels.as_ptr().cast_mut(),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<image::RgbaImage> for IconImage {
+    fn from(value: image::RgbaImage) -> Self {
+        Self {
+            width: value.width(),
+            height: value.height(),
+            pixels: value.into_raw(),
+        }
+    }
+}
@@ -1,7 +1,53 @@
+mod allocator;
+mod attribute;
 mod context;
 mod error;
+mod event;
+mod fixed_step;
+mod frame_stats;
+#[cfg(feature = "raw-window-handle")]
+mod handle;
+mod icon;
+mod loader;
+#[cfg(any(
+    all(
+        unix,
+        not(target_os = "macos"),
+        any(feature = "x11", feature = "wayland")
+    ),
+    feature = "glx",
+    feature = "egl",
+    feature = "osmesa"
+))]
+mod native;
+mod offscreen;
+#[cfg(feature = "osmesa")]
+mod osmesa;
+mod render_context;
+mod state;
+mod time;
+mod version;
+mod vulkan;
 mod window;
 
+pub use allocator::*;
+pub use attribute::*;
 pub use context::*;
 pub use error::*;
+pub use event::*;
+pub use fixed_step::*;
+pub use frame_stats::*;
+pub use icon::*;
+pub use loader::*;
+pub use offscreen::*;
+#[cfg(feature = "osmesa")]
+pub use osmesa::*;
+pub use render_context::*;
+pub use time::*;
+pub use version::*;
 pub use window::*;
+
+#[cfg(feature = "vulkan")]
+pub use ash;
+#[cfg(feature = "raw-window-handle")]
+pub use raw_window_handle;
@@ -0,0 +1,28 @@
+use glfw_rs_sys as glfw_sys;
+use std::{
+    ffi::{CString, c_void},
+    ptr,
+};
+
+pub type LoadProc = fn(name: *const i8) -> *const c_void;
+pub type SafeLoadProc = fn(name: &str) -> *const c_void;
+
+pub(crate) fn load_proc(name: *const i8) -> *const c_void {
+    match unsafe { glfw_sys::glfwGetProcAddress(name) } {
+        Some(ptr) => ptr as *const c_void,
+        None => ptr::null_mut(),
+    }
+}
+
+/// Get the address of an OpenGL function of the context current on this thread,
+/// or null if it doesn't exist. Works the same for windows, [`OffscreenContext`]s
+/// and [`RenderContext`]s, pass it to your OpenGL loader after making one current
+///
+/// [`OffscreenContext`]: crate::OffscreenContext
+/// [`RenderContext`]: crate::RenderContext
+pub fn safe_load_proc(name: &str) -> *const c_void {
+    match CString::new(name) {
+        Ok(name) => load_proc(name.as_ptr()),
+        Err(_) => ptr::null_mut(),
+    }
+}
@@ -0,0 +1,149 @@
+//! Access to the native handles behind GLFW objects. Every accessor returns
+//! [`None`] when GLFW isn't running on the matching platform or the window's
+//! context wasn't created with the matching API.
+
+use crate::{Platform, Window, version::platform};
+use glfw_rs_sys as glfw_sys;
+
+#[cfg(any(
+    all(
+        unix,
+        not(target_os = "macos"),
+        any(feature = "x11", feature = "wayland")
+    ),
+    feature = "egl"
+))]
+use crate::Context;
+
+#[cfg(any(feature = "glx", feature = "egl", feature = "osmesa"))]
+use crate::ContextCreationApi;
+
+fn non_null<T>(ptr: *mut T) -> Option<*mut T> {
+    (!ptr.is_null()).then_some(ptr)
+}
+
+#[cfg(all(feature = "x11", unix, not(target_os = "macos")))]
+impl Context {
+    /// Get the X11 `Display` used by GLFW
+    pub fn x11_display(&self) -> Option<*mut glfw_sys::Display> {
+        if platform() != Ok(Platform::X11) {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetX11Display() })
+    }
+}
+
+#[cfg(all(feature = "x11", unix, not(target_os = "macos")))]
+impl<C> Window<C> {
+    /// Get the X11 `Window` of this window
+    pub fn x11_window(&self) -> Option<glfw_sys::Window> {
+        if platform() != Ok(Platform::X11) {
+            return None;
+        }
+        let window = unsafe { glfw_sys::glfwGetX11Window(self.raw()) };
+        (window != 0).then_some(window)
+    }
+}
+
+#[cfg(all(feature = "wayland", unix, not(target_os = "macos")))]
+impl Context {
+    /// Get the Wayland `wl_display` used by GLFW
+    pub fn wayland_display(&self) -> Option<*mut glfw_sys::wl_display> {
+        if platform() != Ok(Platform::Wayland) {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetWaylandDisplay() })
+    }
+}
+
+#[cfg(all(feature = "wayland", unix, not(target_os = "macos")))]
+impl<C> Window<C> {
+    /// Get the Wayland `wl_surface` of this window
+    pub fn wayland_surface(&self) -> Option<*mut glfw_sys::wl_surface> {
+        if platform() != Ok(Platform::Wayland) {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetWaylandWindow(self.raw()) })
+    }
+}
+
+#[cfg(feature = "glx")]
+impl<C> Window<C> {
+    fn uses_glx(&self) -> bool {
+        platform() == Ok(Platform::X11)
+            && self.context_creation_api() == Some(ContextCreationApi::Native)
+    }
+
+    /// Get the `GLXContext` of this window
+    pub fn glx_context(&self) -> Option<glfw_sys::GLXContext> {
+        if !self.uses_glx() {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetGLXContext(self.raw()) })
+    }
+
+    /// Get the `GLXWindow` of this window
+    pub fn glx_window(&self) -> Option<glfw_sys::GLXWindow> {
+        if !self.uses_glx() {
+            return None;
+        }
+        let window = unsafe { glfw_sys::glfwGetGLXWindow(self.raw()) };
+        (window != 0).then_some(window)
+    }
+}
+
+#[cfg(feature = "egl")]
+impl Context {
+    /// Get the `EGLDisplay` used by GLFW, [`None`] if EGL wasn't loaded
+    pub fn egl_display(&self) -> Option<glfw_sys::EGLDisplay> {
+        non_null(unsafe { glfw_sys::glfwGetEGLDisplay() })
+    }
+}
+
+#[cfg(feature = "egl")]
+impl<C> Window<C> {
+    fn uses_egl(&self) -> bool {
+        // native contexts are created with EGL on Wayland
+        match self.context_creation_api() {
+            Some(ContextCreationApi::Egl) => true,
+            Some(ContextCreationApi::Native) => platform() == Ok(Platform::Wayland),
+            _ => false,
+        }
+    }
+
+    /// Get the `EGLContext` of this window
+    pub fn egl_context(&self) -> Option<glfw_sys::EGLContext> {
+        if !self.uses_egl() {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetEGLContext(self.raw()) })
+    }
+
+    /// Get the `EGLSurface` of this window
+    pub fn egl_surface(&self) -> Option<glfw_sys::EGLSurface> {
+        if !self.uses_egl() {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetEGLSurface(self.raw()) })
+    }
+}
+
+#[cfg(feature = "osmesa")]
+impl<C> Window<C> {
+    pub(crate) fn uses_osmesa(&self) -> bool {
+        // native contexts are created with OSMesa on the null platform
+        match self.context_creation_api() {
+            Some(ContextCreationApi::OsMesa) => true,
+            Some(ContextCreationApi::Native) => platform() == Ok(Platform::Null),
+            _ => false,
+        }
+    }
+
+    /// Get the `OSMesaContext` of this window
+    pub fn osmesa_context(&self) -> Option<glfw_sys::OSMesaContext> {
+        if !self.uses_osmesa() {
+            return None;
+        }
+        non_null(unsafe { glfw_sys::glfwGetOSMesaContext(self.raw()) })
+    }
+}
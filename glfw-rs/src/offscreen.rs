@@ -0,0 +1,84 @@
+use crate::{Context, CurrentContextGuard, Window};
+use glfw_rs_sys as glfw_sys;
+use glfw_sys::GLFWwindow;
+use std::{
+    ffi::CString,
+    mem,
+    ptr::{self, NonNull},
+    sync::{Mutex, MutexGuard},
+};
+
+/// Offscreen contexts which were dropped on another thread. They are destroyed
+/// on the main thread the next time the events are polled. Stored as addresses
+/// so the list is `Send`.
+static PENDING_DESTROY: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+/// An OpenGL context without a visible window, which shares its objects
+/// (textures, buffers, shaders...) with a [`Window`]. Create it on the main thread
+/// with [`Context::offscreen_context`], then send it to a worker thread to load
+/// resources in the background.
+///
+/// [`Context::offscreen_context`]: crate::Context::offscreen_context
+pub struct OffscreenContext {
+    raw: NonNull<GLFWwindow>,
+}
+
+// SAFETY: GLFW allows making a context current on any thread, as long as it's
+// current on one thread at a time. `OffscreenContext` is not `Sync`, so only the
+// thread owning it can make it current, and the window itself is destroyed on
+// the main thread.
+unsafe impl Send for OffscreenContext {}
+
+impl OffscreenContext {
+    pub(crate) fn new(shared: &Window) -> Option<Self> {
+        let name = CString::default();
+        let handle = unsafe {
+            glfw_sys::glfwWindowHint(glfw_sys::GLFW_VISIBLE as i32, glfw_sys::GLFW_FALSE as i32);
+            let handle =
+                glfw_sys::glfwCreateWindow(1, 1, name.as_ptr(), ptr::null_mut(), shared.raw());
+            glfw_sys::glfwWindowHint(glfw_sys::GLFW_VISIBLE as i32, glfw_sys::GLFW_TRUE as i32);
+            handle
+        };
+
+        Some(Self {
+            raw: NonNull::new(handle)?,
+        })
+    }
+
+    /// Make this context current on this thread until the returned guard is dropped
+    #[must_use = "the previous context is restored when the guard is dropped"]
+    pub fn make_current(&self) -> CurrentContextGuard<'_> {
+        CurrentContextGuard::new(self.raw.as_ptr())
+    }
+}
+
+impl Drop for OffscreenContext {
+    fn drop(&mut self) {
+        let raw = self.raw.as_ptr();
+        // a context must not be current on any thread when it's destroyed
+        if unsafe { glfw_sys::glfwGetCurrentContext() } == raw {
+            unsafe { glfw_sys::glfwMakeContextCurrent(ptr::null_mut()) };
+        }
+
+        if Context::get().is_some_and(Context::is_main_thread) {
+            unsafe { glfw_sys::glfwDestroyWindow(raw) };
+        } else {
+            pending_destroy().push(raw as usize);
+        }
+    }
+}
+
+fn pending_destroy() -> MutexGuard<'static, Vec<usize>> {
+    PENDING_DESTROY
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+}
+
+/// Destroy the offscreen contexts which were dropped. Must be called on the main
+/// thread.
+pub(crate) fn destroy_pending() {
+    let pending = mem::take(&mut *pending_destroy());
+    for raw in pending {
+        unsafe { glfw_sys::glfwDestroyWindow(raw as *mut GLFWwindow) };
+    }
+}
@@ -0,0 +1,215 @@
+use crate::{Window, state};
+use glfw_rs_sys as glfw_sys;
+use std::{ffi::c_void, ptr, slice};
+
+// the pixel formats from `osmesa.h`, GLFW always uses `OSMESA_RGBA`
+const OSMESA_RGBA: i32 = 0x1908;
+const OSMESA_BGRA: i32 = 0x1;
+const OSMESA_ARGB: i32 = 0x2;
+const OSMESA_RGB: i32 = 0x1907;
+const OSMESA_BGR: i32 = 0x4;
+
+/// A copy of the color buffer of an OSMesa context, see
+/// [`Window::osmesa_color_buffer`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ColorBuffer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl ColorBuffer {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// 8-bit RGBA pixels, arranged left-to-right, top-to-bottom
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn into_pixels(self) -> Vec<u8> {
+        self.pixels
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<ColorBuffer> for image::RgbaImage {
+    fn from(value: ColorBuffer) -> Self {
+        image::RgbaImage::from_raw(value.width, value.height, value.pixels)
+            .expect("the buffer size matches its dimensions")
+    }
+}
+
+/// A copy of the depth buffer of an OSMesa context, see
+/// [`Window::osmesa_depth_buffer`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DepthBuffer {
+    width: u32,
+    height: u32,
+    bytes_per_value: u32,
+    depth_bits: u32,
+    values: Vec<u32>,
+}
+
+impl DepthBuffer {
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// The size of a depth value in the context, either 2 or 4 bytes
+    pub fn bytes_per_value(&self) -> u32 {
+        self.bytes_per_value
+    }
+
+    /// The amount of low bits of a value which hold the depth. A 24-bit depth is
+    /// packed with the stencil, which is masked out of [`DepthBuffer::values`]
+    pub fn depth_bits(&self) -> u32 {
+        self.depth_bits
+    }
+
+    /// The largest depth value, i.e. the far plane
+    pub fn max_value(&self) -> u32 {
+        u32::MAX >> (32 - self.depth_bits)
+    }
+
+    /// The depth values, arranged left-to-right, top-to-bottom
+    pub fn values(&self) -> &[u32] {
+        &self.values
+    }
+}
+
+/// Copy the rows of an OSMesa buffer, which are stored bottom-to-top like in
+/// OpenGL, so that they're top-to-bottom
+fn flip_rows<T: Copy>(data: &[T], row_len: usize) -> Vec<T> {
+    data.rchunks_exact(row_len).flatten().copied().collect()
+}
+
+impl Window {
+    /// Copy the color buffer of the window's OSMesa context. Call `glFinish`
+    /// first to make sure rendering is done. Returns [`None`] if the context
+    /// wasn't created with OSMesa, see [`Context::context_creation_api`].
+    ///
+    /// # Panics
+    /// If the context was split off with [`Window::render_context`]
+    ///
+    /// [`Context::context_creation_api`]: crate::Context::context_creation_api
+    pub fn osmesa_color_buffer(&self) -> Option<ColorBuffer> {
+        self.assert_not_split();
+        if !self.uses_osmesa() {
+            return None;
+        }
+
+        let (mut width, mut height, mut format) = (0, 0, 0);
+        let mut buffer: *mut c_void = ptr::null_mut();
+        let found = unsafe {
+            glfw_sys::glfwGetOSMesaColorBuffer(
+                self.raw(),
+                &mut width,
+                &mut height,
+                &mut format,
+                &mut buffer,
+            )
+        };
+        if found == 0 || buffer.is_null() || width <= 0 || height <= 0 {
+            return None;
+        }
+
+        // the position of red, green, blue and alpha in a pixel
+        let (channels, order): (usize, [Option<usize>; 4]) = match format {
+            OSMESA_RGBA => (4, [Some(0), Some(1), Some(2), Some(3)]),
+            OSMESA_BGRA => (4, [Some(2), Some(1), Some(0), Some(3)]),
+            OSMESA_ARGB => (4, [Some(1), Some(2), Some(3), Some(0)]),
+            OSMESA_RGB => (3, [Some(0), Some(1), Some(2), None]),
+            OSMESA_BGR => (3, [Some(2), Some(1), Some(0), None]),
+            _ => return None,
+        };
+
+        let (width, height) = (width as usize, height as usize);
+        let raw = unsafe { slice::from_raw_parts(buffer.cast::<u8>(), width * height * channels) };
+        let pixels = flip_rows(raw, width * channels)
+            .chunks_exact(channels)
+            .flat_map(|pixel| order.map(|channel| channel.map_or(u8::MAX, |i| pixel[i])))
+            .collect();
+
+        Some(ColorBuffer {
+            width: width as u32,
+            height: height as u32,
+            pixels,
+        })
+    }
+
+    /// Copy the depth buffer of the window's OSMesa context. Returns [`None`] if
+    /// the context wasn't created with OSMesa or has no depth buffer. The values
+    /// are decoded with the depth bits set by [`Context::depth_bits`].
+    ///
+    /// # Panics
+    /// If the context was split off with [`Window::render_context`]
+    ///
+    /// [`Context::depth_bits`]: crate::Context::depth_bits
+    pub fn osmesa_depth_buffer(&self) -> Option<DepthBuffer> {
+        self.assert_not_split();
+        if !self.uses_osmesa() {
+            return None;
+        }
+
+        let (mut width, mut height, mut bytes_per_value) = (0, 0, 0);
+        let mut buffer: *mut c_void = ptr::null_mut();
+        let found = unsafe {
+            glfw_sys::glfwGetOSMesaDepthBuffer(
+                self.raw(),
+                &mut width,
+                &mut height,
+                &mut bytes_per_value,
+                &mut buffer,
+            )
+        };
+        if found == 0 || buffer.is_null() || width <= 0 || height <= 0 {
+            return None;
+        }
+        if !matches!(bytes_per_value, 2 | 4) {
+            return None;
+        }
+
+        // Mesa packs a 24-bit depth with the 8-bit stencil into 4 bytes, with the
+        // depth in the low bits
+        let value_bits = bytes_per_value as u32 * 8;
+        let depth_bits = match state::with_state(self.raw(), |state| state.depth_bits) as u32 {
+            0 => value_bits,
+            bits => bits.min(value_bits),
+        };
+        let mask = u32::MAX >> (32 - depth_bits);
+
+        let (width, height) = (width as usize, height as usize);
+        let len = width * height;
+        let values = if bytes_per_value == 2 {
+            let raw = unsafe { slice::from_raw_parts(buffer.cast::<u16>(), len) };
+            flip_rows(raw, width)
+                .into_iter()
+                .map(|value| u32::from(value) & mask)
+                .collect()
+        } else {
+            let raw = unsafe { slice::from_raw_parts(buffer.cast::<u32>(), len) };
+            flip_rows(raw, width)
+                .into_iter()
+                .map(|value| value & mask)
+                .collect()
+        };
+
+        Some(DepthBuffer {
+            width: width as u32,
+            height: height as u32,
+            bytes_per_value: bytes_per_value as u32,
+            depth_bits,
+            values,
+        })
+    }
+}
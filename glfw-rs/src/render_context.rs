@@ -0,0 +1,102 @@
+use glfw_rs_sys as glfw_sys;
+use glfw_sys::GLFWwindow;
+use std::{
+    cell::Cell,
+    ptr::{self, NonNull},
+    sync::atomic::{AtomicBool, Ordering},
+    thread::{self, ThreadId},
+};
+
+/// The OpenGL context of a [`Window`], split from it with [`Window::render_context`]
+/// so rendering can happen on another thread, while the window itself and its
+/// events stay on the main thread.
+///
+/// The context can be current on only one thread at a time. Moving it to
+/// another thread while it's current panics on the next
+/// [`RenderContext::make_current`], call [`RenderContext::release`] first. The
+/// handle borrows the window, so use [`std::thread::scope`] to send it to
+/// another thread. If it's dropped while still current on another thread, the
+/// window never gets its context back.
+///
+/// [`Window`]: crate::Window
+/// [`Window::render_context`]: crate::Window::render_context
+pub struct RenderContext<'w> {
+    raw: NonNull<GLFWwindow>,
+    split: &'w AtomicBool,
+    current_on: Cell<Option<ThreadId>>,
+}
+
+// SAFETY: the only window functions called through this handle are the ones GLFW
+// allows on any thread, and `current_on` makes sure the context is current on at
+// most one thread. It's not `Sync`, so only one thread can use it at a time.
+unsafe impl Send for RenderContext<'_> {}
+
+impl<'w> RenderContext<'w> {
+    pub(crate) fn new(raw: NonNull<GLFWwindow>, split: &'w AtomicBool) -> Self {
+        Self {
+            raw,
+            split,
+            current_on: Cell::new(None),
+        }
+    }
+
+    /// Make the context current on this thread.
+    ///
+    /// # Panics
+    /// If the context is still current on another thread
+    pub fn make_current(&self) {
+        let this = thread::current().id();
+        match self.current_on.get() {
+            Some(thread) if thread == this => (),
+            Some(thread) => panic!(
+                "the render context is still current on {thread:?}, call `release` before moving it to another thread"
+            ),
+            None => {
+                unsafe { glfw_sys::glfwMakeContextCurrent(self.raw.as_ptr()) };
+                self.current_on.set(Some(this));
+            }
+        }
+    }
+
+    /// Detach the context from this thread, so it can be made current on another
+    /// one. Does nothing if the context isn't current on this thread.
+    pub fn release(&self) {
+        if self.is_current() {
+            unsafe { glfw_sys::glfwMakeContextCurrent(ptr::null_mut()) };
+            self.current_on.set(None);
+        }
+    }
+
+    /// Check if the context is current on this thread
+    pub fn is_current(&self) -> bool {
+        self.current_on.get() == Some(thread::current().id())
+    }
+
+    /// Swap the buffers of the window
+    pub fn swap_buffers(&self) {
+        unsafe { glfw_sys::glfwSwapBuffers(self.raw.as_ptr()) }
+    }
+
+    /// Set the amount of frames to wait before swapping the buffers. Makes the
+    /// context current on this thread.
+    pub fn set_swap_interval(&self, interval: i32) {
+        self.make_current();
+        unsafe { glfw_sys::glfwSwapInterval(interval) }
+    }
+}
+
+impl Drop for RenderContext<'_> {
+    fn drop(&mut self) {
+        // the context is still current there, so the window must not get it back
+        if self
+            .current_on
+            .get()
+            .is_some_and(|thread| thread != thread::current().id())
+        {
+            return;
+        }
+
+        self.release();
+        self.split.store(false, Ordering::Release);
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{FrameStats, WindowId, window::ContextLock};
+use glfw_rs_sys as glfw_sys;
+use glfw_sys::GLFWwindow;
+use std::{cell::RefCell, collections::HashMap, sync::Arc};
+
+/// Called with the window the handler was registered on, see
+/// [`Window::set_close_handler`]
+///
+/// [`Window::set_close_handler`]: crate::Window::set_close_handler
+pub(crate) type CloseHandler = Box<dyn FnMut(*mut GLFWwindow) -> bool>;
+
+/// Rust-side state of a window, like the closures registered on it. GLFW callbacks
+/// only run on the main thread, so the state is kept thread local.
+#[derive(Default)]
+pub(crate) struct WindowState {
+    pub close_handler: Option<CloseHandler>,
+    pub redraw_requested: bool,
+    pub frame_stats: Option<FrameStats>,
+    /// The swap interval set with [`Window::set_swap_interval`], if any
+    ///
+    /// [`Window::set_swap_interval`]: crate::Window::set_swap_interval
+    pub swap_interval: Option<i32>,
+    pub context_lock: Arc<ContextLock>,
+    /// The depth bits requested when the window was created
+    pub depth_bits: u8,
+}
+
+thread_local! {
+    static STATES: RefCell<HashMap<*mut GLFWwindow, WindowState>> = RefCell::new(HashMap::new());
+}
+
+/// Run `f` with the state of `window`, creating it if needed. `f` must not call
+/// user code, take the closure out of the state instead.
+pub(crate) fn with_state<R>(window: *mut GLFWwindow, f: impl FnOnce(&mut WindowState) -> R) -> R {
+    STATES.with_borrow_mut(|states| f(states.entry(window).or_default()))
+}
+
+/// Get the context lock of `window` without creating its state, [`None`] for
+/// windows which aren't tracked, like offscreen contexts
+pub(crate) fn context_lock(window: *mut GLFWwindow) -> Option<Arc<ContextLock>> {
+    STATES.with_borrow(|states| states.get(&window).map(|state| state.context_lock.clone()))
+}
+
+pub(crate) fn remove_state(window: *mut GLFWwindow) {
+    // the state may contain closures which capture other windows, so drop it
+    // after the map is no longer borrowed
+    let state = STATES.with_borrow_mut(|states| states.remove(&window));
+    drop(state);
+}
+
+/// Take the windows which requested a redraw, clearing the requests.
+pub(crate) fn take_redraw_requests() -> Vec<WindowId> {
+    STATES.with_borrow_mut(|states| {
+        states
+            .iter_mut()
+            .filter(|(_, state)| state.redraw_requested)
+            .map(|(window, state)| {
+                state.redraw_requested = false;
+                WindowId::from_raw(*window)
+            })
+            .collect()
+    })
+}
+
+/// Check if any window requested a redraw which wasn't handled yet.
+pub(crate) fn has_redraw_requests() -> bool {
+    STATES.with_borrow(|states| states.values().any(|state| state.redraw_requested))
+}
+
+/// Check if there are windows alive and every one of them should close.
+pub(crate) fn all_windows_should_close() -> bool {
+    STATES.with_borrow(|states| {
+        !states.is_empty()
+            && states
+                .keys()
+                .all(|window| unsafe { glfw_sys::glfwWindowShouldClose(*window) } == 1)
+    })
+}
@@ -0,0 +1,123 @@
+use glfw_rs_sys as glfw_sys;
+use std::{
+    ops::{Add, AddAssign, Sub, SubAssign},
+    sync::OnceLock,
+    time::Duration,
+};
+
+static FREQUENCY: OnceLock<u64> = OnceLock::new();
+
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// The frequency of the raw GLFW timer, in ticks per second
+pub(crate) fn frequency() -> u64 {
+    *FREQUENCY.get_or_init(|| unsafe { glfw_sys::glfwGetTimerFrequency() })
+}
+
+fn ticks_to_duration(ticks: u64) -> Duration {
+    let nanos = ticks as u128 * NANOS_PER_SEC / frequency() as u128;
+    Duration::new(
+        (nanos / NANOS_PER_SEC) as u64,
+        (nanos % NANOS_PER_SEC) as u32,
+    )
+}
+
+fn duration_to_ticks(duration: Duration) -> Option<u64> {
+    let ticks = duration.as_nanos() * frequency() as u128 / NANOS_PER_SEC;
+    ticks.try_into().ok()
+}
+
+/// A measurement of the raw GLFW timer, similar to [`std::time::Instant`]. Unlike
+/// [`Context::get_time`] it is never converted to floating point, so it doesn't
+/// lose precision, and it isn't affected by [`Context::set_time`]. Created with
+/// [`Context::now`].
+///
+/// [`Context::get_time`]: crate::Context::get_time
+/// [`Context::set_time`]: crate::Context::set_time
+/// [`Context::now`]: crate::Context::now
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GlfwInstant {
+    ticks: u64,
+}
+
+impl GlfwInstant {
+    pub(crate) fn now() -> Self {
+        Self {
+            ticks: unsafe { glfw_sys::glfwGetTimerValue() },
+        }
+    }
+
+    /// The raw value of the timer, see [`Context::timer_frequency`]
+    ///
+    /// [`Context::timer_frequency`]: crate::Context::timer_frequency
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// The time elapsed since this instant was created
+    pub fn elapsed(&self) -> Duration {
+        Self::now().saturating_duration_since(*self)
+    }
+
+    /// The time elapsed from `earlier` to this instant, or [`None`] if `earlier`
+    /// is later than this instant
+    pub fn checked_duration_since(&self, earlier: GlfwInstant) -> Option<Duration> {
+        let ticks = self.ticks.checked_sub(earlier.ticks)?;
+        Some(ticks_to_duration(ticks))
+    }
+
+    /// The time elapsed from `earlier` to this instant, or zero if `earlier` is
+    /// later than this instant
+    pub fn saturating_duration_since(&self, earlier: GlfwInstant) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+
+    pub fn checked_add(&self, duration: Duration) -> Option<GlfwInstant> {
+        let ticks = self.ticks.checked_add(duration_to_ticks(duration)?)?;
+        Some(Self { ticks })
+    }
+
+    pub fn checked_sub(&self, duration: Duration) -> Option<GlfwInstant> {
+        let ticks = self.ticks.checked_sub(duration_to_ticks(duration)?)?;
+        Some(Self { ticks })
+    }
+}
+
+impl Add<Duration> for GlfwInstant {
+    type Output = GlfwInstant;
+
+    fn add(self, rhs: Duration) -> Self::Output {
+        self.checked_add(rhs)
+            .expect("overflow when adding duration to instant")
+    }
+}
+
+impl AddAssign<Duration> for GlfwInstant {
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for GlfwInstant {
+    type Output = GlfwInstant;
+
+    fn sub(self, rhs: Duration) -> Self::Output {
+        self.checked_sub(rhs)
+            .expect("overflow when subtracting duration from instant")
+    }
+}
+
+impl SubAssign<Duration> for GlfwInstant {
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub<GlfwInstant> for GlfwInstant {
+    type Output = Duration;
+
+    /// Same as [`GlfwInstant::saturating_duration_since`]
+    fn sub(self, rhs: GlfwInstant) -> Self::Output {
+        self.saturating_duration_since(rhs)
+    }
+}
@@ -0,0 +1,121 @@
+use crate::{Context, Error, InitError};
+use glfw_rs_sys as glfw_sys;
+use std::{ffi::CStr, fmt, sync::OnceLock};
+
+static RUNTIME_VERSION: OnceLock<Version> = OnceLock::new();
+
+/// A version of the GLFW library, see [`Context::version`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    pub major: i32,
+    pub minor: i32,
+    pub revision: i32,
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
+
+/// A platform GLFW can run on. A GLFW library may support several of them,
+/// e.g. both X11 and Wayland, and picks one when it's initialized
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum Platform {
+    Win32 = glfw_sys::GLFW_PLATFORM_WIN32,
+    Cocoa = glfw_sys::GLFW_PLATFORM_COCOA,
+    Wayland = glfw_sys::GLFW_PLATFORM_WAYLAND,
+    X11 = glfw_sys::GLFW_PLATFORM_X11,
+    /// The headless platform, which has no windowing system
+    Null = glfw_sys::GLFW_PLATFORM_NULL,
+}
+
+impl Platform {
+    pub(crate) fn from_raw(raw: i32) -> Option<Self> {
+        [
+            Self::Win32,
+            Self::Cocoa,
+            Self::Wayland,
+            Self::X11,
+            Self::Null,
+        ]
+        .into_iter()
+        .find(|platform| *platform as i32 == raw)
+    }
+}
+
+/// Check that the GLFW library being used is at least `major.minor`, the
+/// bindings are generated from newer headers than some libraries
+pub(crate) fn require(major: i32, minor: i32) -> Result<(), Error> {
+    let version = runtime_version();
+    if (version.major, version.minor) < (major, minor) {
+        return Err(Error::FeatureUnavailable);
+    }
+    Ok(())
+}
+
+/// Load the GLFW library when it's loaded at runtime, so the functions which can
+/// be called before [`Context::init`] fail instead of panicking without it
+fn ensure_loaded() -> Result<(), InitError> {
+    #[cfg(feature = "dlopen")]
+    glfw_sys::load().map_err(InitError::LibraryUnavailable)?;
+    Ok(())
+}
+
+fn runtime_version() -> Version {
+    *RUNTIME_VERSION.get_or_init(|| {
+        let (mut major, mut minor, mut revision) = (0, 0, 0);
+        unsafe { glfw_sys::glfwGetVersion(&mut major, &mut minor, &mut revision) };
+        Version {
+            major,
+            minor,
+            revision,
+        }
+    })
+}
+
+/// The platform GLFW was initialized with. Requires GLFW 3.4
+pub(crate) fn platform() -> Result<Platform, Error> {
+    require(3, 4)?;
+    let platform = unsafe { glfw_sys::glfwGetPlatform() };
+    Platform::from_raw(platform).ok_or(Error::NotInitialized)
+}
+
+impl Context {
+    /// Get the version of the GLFW library being used, which may be older than
+    /// the headers the bindings were generated from. Can be called before
+    /// [`Context::init`], returns [`InitError::LibraryUnavailable`] if the library
+    /// can't be loaded at runtime
+    pub fn version() -> Result<Version, InitError> {
+        ensure_loaded()?;
+        Ok(runtime_version())
+    }
+
+    /// Get the version string of the GLFW library being used, which describes the
+    /// platforms and options it was built with. Can be called before
+    /// [`Context::init`], returns [`InitError::LibraryUnavailable`] if the library
+    /// can't be loaded at runtime
+    pub fn version_string() -> Result<String, InitError> {
+        ensure_loaded()?;
+        let version = unsafe { CStr::from_ptr(glfw_sys::glfwGetVersionString()) };
+        Ok(version.to_string_lossy().to_string())
+    }
+
+    /// Get the platform GLFW was initialized with. Returns
+    /// [`Error::FeatureUnavailable`] if the library is older than GLFW 3.4
+    pub fn platform(&self) -> Result<Platform, Error> {
+        platform()
+    }
+
+    /// Check if the GLFW library was built with support for `platform`. Can be
+    /// called before [`Context::init`]. Returns [`InitError::LibraryUnavailable`]
+    /// if the library can't be loaded at runtime, and
+    /// [`InitError::FeatureUnavailable`] if it's older than GLFW 3.4
+    pub fn platform_supported(platform: Platform) -> Result<bool, InitError> {
+        ensure_loaded()?;
+        require(3, 4).map_err(|_| InitError::FeatureUnavailable)?;
+        Ok(unsafe { glfw_sys::glfwPlatformSupported(platform as i32) }
+            == glfw_sys::GLFW_TRUE as i32)
+    }
+}
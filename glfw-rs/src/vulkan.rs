@@ -0,0 +1,93 @@
+use crate::Context;
+use glfw_rs_sys as glfw_sys;
+use std::{ffi::CStr, slice};
+
+#[cfg(feature = "vulkan")]
+use crate::{NoContext, Window};
+#[cfg(feature = "vulkan")]
+use ash::vk::{self, Handle};
+#[cfg(feature = "vulkan")]
+use std::mem;
+
+impl Context {
+    /// Check if a Vulkan loader and at least one minimally functional driver were
+    /// found
+    pub fn vulkan_supported(&self) -> bool {
+        unsafe { glfw_sys::glfwVulkanSupported() == 1 }
+    }
+
+    /// Get the Vulkan instance extensions needed to create surfaces for windows.
+    /// Returns [`None`] if Vulkan is unavailable or can't present to windows
+    pub fn required_instance_extensions(&self) -> Option<Vec<&'static CStr>> {
+        let mut count = 0;
+        let names = unsafe { glfw_sys::glfwGetRequiredInstanceExtensions(&mut count) };
+        if names.is_null() {
+            return None;
+        }
+
+        // the names are owned by GLFW and live until it's terminated, and the
+        // context is never dropped
+        let names = unsafe { slice::from_raw_parts(names, count as usize) };
+        let names = names
+            .iter()
+            .map(|name| unsafe { CStr::from_ptr(*name) })
+            .collect();
+        Some(names)
+    }
+}
+
+#[cfg(feature = "vulkan")]
+impl Context {
+    /// Get the address of a Vulkan function for `instance`, using the Vulkan loader
+    /// found by GLFW. Pass a null instance for the functions which don't take one
+    pub fn instance_proc_address(
+        &self,
+        instance: vk::Instance,
+        name: &CStr,
+    ) -> vk::PFN_vkVoidFunction {
+        let instance = instance.as_raw() as usize as glfw_sys::VkInstance;
+        let proc = unsafe { glfw_sys::glfwGetInstanceProcAddress(instance, name.as_ptr()) };
+        // both are nullable pointers to functions without arguments
+        unsafe { mem::transmute::<glfw_sys::GLFWvkproc, vk::PFN_vkVoidFunction>(proc) }
+    }
+
+    /// Check if a queue family of a physical device can present images to windows
+    pub fn physical_device_presentation_support(
+        &self,
+        instance: &ash::Instance,
+        device: vk::PhysicalDevice,
+        queue_family: u32,
+    ) -> bool {
+        let instance = instance.handle().as_raw() as usize as glfw_sys::VkInstance;
+        let device = device.as_raw() as usize as glfw_sys::VkPhysicalDevice;
+        unsafe {
+            glfw_sys::glfwGetPhysicalDevicePresentationSupport(instance, device, queue_family) == 1
+        }
+    }
+}
+
+#[cfg(feature = "vulkan")]
+impl Window<NoContext> {
+    /// Create a Vulkan surface for this window. The instance must have been created
+    /// with the extensions from [`Context::required_instance_extensions`].
+    pub fn create_surface(
+        &self,
+        instance: &ash::Instance,
+        allocator: Option<&vk::AllocationCallbacks>,
+    ) -> Result<vk::SurfaceKHR, vk::Result> {
+        let raw_instance = instance.handle().as_raw() as usize as glfw_sys::VkInstance;
+        let allocator = allocator.map_or(std::ptr::null(), |allocator| {
+            (allocator as *const vk::AllocationCallbacks).cast()
+        });
+
+        let mut surface = 0;
+        let result = unsafe {
+            glfw_sys::glfwCreateWindowSurface(raw_instance, self.raw(), allocator, &mut surface)
+        };
+
+        match vk::Result::from_raw(result) {
+            vk::Result::SUCCESS => Ok(vk::SurfaceKHR::from_raw(surface)),
+            err => Err(err),
+        }
+    }
+}
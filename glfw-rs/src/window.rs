@@ -1,17 +1,54 @@
+use crate::{
+    ClientApi, ContextCreationApi, ContextInfo, ContextRobustness, Error, FrameStats, GlfwInstant,
+    IconImage, LoadProc, OpenGlProfile, ReleaseBehavior, RenderContext, SafeLoadProc,
+    SettableAttribute, ValueAttribute, WindowAttribute, WindowId, context, event,
+    loader::{load_proc, safe_load_proc},
+    state, version,
+};
 use glfw_rs_sys as glfw_sys;
 use glfw_sys::{GLFWmonitor, GLFWwindow};
 use std::{
-    ffi::{CString, c_void},
+    ffi::{CStr, CString, c_void},
+    marker::PhantomData,
     ptr::{self, NonNull},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
 };
 
-/// A GLFW window
-pub struct Window {
+/// A GLFW window. `C` tells whether the window has an OpenGL context, the methods
+/// which need one are only available for [`GlContext`] windows.
+pub struct Window<C = GlContext> {
     raw: NonNull<GLFWwindow>,
     should_drop: bool,
+    lock: Arc<ContextLock>,
+    marker: PhantomData<C>,
 }
 
-impl Window {
+/// Tracks who uses the OpenGL context of a window. Shared by every [`Window`]
+/// pointing to it, including the borrowed ones given to callbacks.
+#[derive(Debug, Default)]
+pub(crate) struct ContextLock {
+    /// Set while a [`RenderContext`] of this window exists
+    split: AtomicBool,
+    /// The [`CurrentContextGuard`]s which made the context current, or will make
+    /// it current again when they're dropped
+    guards: AtomicUsize,
+}
+
+/// Marks a [`Window`] with an OpenGL or OpenGL ES context, the default
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GlContext;
+
+/// Marks a [`Window`] without any client API context, for Vulkan or wgpu users.
+/// Created with [`Context::window_no_api`]
+///
+/// [`Context::window_no_api`]: crate::Context::window_no_api
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NoContext;
+
+impl<C> Window<C> {
     fn new_ex<S: AsRef<str>>(
         name: S,
         size: (i32, i32),
@@ -22,9 +59,18 @@ impl Window {
         let handle =
             unsafe { glfw_sys::glfwCreateWindow(size.0, size.1, name.as_ptr(), monitor, shared) };
         let raw = NonNull::new(handle)?;
+        event::install_callbacks(raw.as_ptr());
+        // new windows are drawn once by `Context::run` without asking for it
+        let lock = state::with_state(raw.as_ptr(), |state| {
+            state.redraw_requested = true;
+            state.depth_bits = context::depth_bits_hint();
+            state.context_lock.clone()
+        });
         Some(Self {
             raw,
             should_drop: true,
+            lock,
+            marker: PhantomData,
         })
     }
 
@@ -47,17 +93,36 @@ impl Window {
         )
     }
 
-    /// Create a window from a raw pointer. Note that the window created this way will
-    /// not be dropped. Use this function in callbacks
-    pub unsafe fn from_raw(ptr: *mut GLFWwindow) -> Self {
+    /// Same as [`Window::from_raw`], for any kind of window.
+    pub(crate) unsafe fn from_raw_borrowed(ptr: *mut GLFWwindow) -> Self {
         Self {
             raw: unsafe { NonNull::new_unchecked(ptr) },
             should_drop: false,
+            lock: state::with_state(ptr, |state| state.context_lock.clone()),
+            marker: PhantomData,
         }
     }
 }
 
-impl Drop for Window {
+impl Window {
+    /// Create a new window whose OpenGL context shares its objects with the context
+    /// of `shared`. Returns [`None`] if the inner GLFW call fails
+    pub(crate) fn new_shared<S: AsRef<str>>(
+        name: S,
+        size: (i32, i32),
+        shared: &Window,
+    ) -> Option<Self> {
+        Self::new_ex(name, size, ptr::null_mut(), shared.raw())
+    }
+
+    /// Create a window from a raw pointer. Note that the window created this way will
+    /// not be dropped. Use this function in callbacks
+    pub unsafe fn from_raw(ptr: *mut GLFWwindow) -> Self {
+        unsafe { Self::from_raw_borrowed(ptr) }
+    }
+}
+
+impl<C> Drop for Window<C> {
     fn drop(&mut self) {
         if self.should_drop {
             if !self.get_user_data_ptr().is_null() {
@@ -65,50 +130,211 @@ impl Drop for Window {
             }
 
             unsafe { glfw_sys::glfwDestroyWindow(self.raw.as_ptr()) }
+            state::remove_state(self.raw.as_ptr());
+        }
+    }
+}
+
+/// Restores the previously current OpenGL context when dropped, see
+/// [`Window::make_current`]
+pub struct CurrentContextGuard<'a> {
+    previous: *mut GLFWwindow,
+    /// The locks of the windows made current and restored, so their contexts
+    /// can't be split off while the guard is alive
+    locks: [Option<Arc<ContextLock>>; 2],
+    marker: PhantomData<&'a ()>,
+}
+
+impl CurrentContextGuard<'_> {
+    pub(crate) fn new(raw: *mut GLFWwindow) -> Self {
+        let previous = unsafe { glfw_sys::glfwGetCurrentContext() };
+        let locks = [state::context_lock(raw), state::context_lock(previous)];
+        for lock in locks.iter().flatten() {
+            lock.guards.fetch_add(1, Ordering::AcqRel);
+        }
+
+        unsafe { glfw_sys::glfwMakeContextCurrent(raw) };
+        Self {
+            previous,
+            locks,
+            marker: PhantomData,
         }
     }
 }
 
-pub type LoadProc = fn(name: *const i8) -> *const c_void;
-pub type SafeLoadProc = fn(name: &str) -> *const c_void;
+impl Drop for CurrentContextGuard<'_> {
+    fn drop(&mut self) {
+        unsafe { glfw_sys::glfwMakeContextCurrent(self.previous) };
+        for lock in self.locks.iter().flatten() {
+            lock.guards.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+}
+
+impl<C> Window<C> {
+    pub(crate) fn raw(&self) -> *mut GLFWwindow {
+        self.raw.as_ptr()
+    }
+
+    /// Get the identifier of the window, used by [`Event`]s
+    ///
+    /// [`Event`]: crate::Event
+    pub fn id(&self) -> WindowId {
+        WindowId::from_raw(self.raw.as_ptr())
+    }
+}
 
 impl Window {
     /// Make this window and its OpenGL context global. Should be called before you
     /// try to initialize you initialize your OpenGL crate.
+    ///
+    /// # Panics
+    /// If the context was split off with [`Window::render_context`]
     pub fn make_global(&self) {
+        self.assert_not_split();
         unsafe { glfw_sys::glfwMakeContextCurrent(self.raw.as_ptr()) }
     }
 
+    /// Make the OpenGL context of this window current on this thread until the
+    /// returned guard is dropped, then restore the context which was current before
+    ///
+    /// # Panics
+    /// If the context was split off with [`Window::render_context`]
+    #[must_use = "the previous context is restored when the guard is dropped"]
+    pub fn make_current(&self) -> CurrentContextGuard<'_> {
+        self.assert_not_split();
+        CurrentContextGuard::new(self.raw.as_ptr())
+    }
+
+    /// Split the OpenGL context off the window, so it can be used on a render
+    /// thread. Returns [`None`] if the context is already split off, or while a
+    /// [`CurrentContextGuard`] would make it current again. Until the returned
+    /// handle is dropped, the context can't be made current through the window.
+    /// If the context is current on this thread, it's detached.
+    pub fn render_context(&self) -> Option<RenderContext<'_>> {
+        if self.lock.guards.load(Ordering::Acquire) > 0
+            || self.lock.split.swap(true, Ordering::AcqRel)
+        {
+            return None;
+        }
+
+        if self.is_current() {
+            unsafe { glfw_sys::glfwMakeContextCurrent(ptr::null_mut()) };
+        }
+
+        Some(RenderContext::new(self.raw, &self.lock.split))
+    }
+
+    pub(crate) fn assert_not_split(&self) {
+        assert!(
+            !self.lock.split.load(Ordering::Acquire),
+            "the context of the window is used by a `RenderContext`"
+        );
+    }
+
+    /// Check if the OpenGL context of this window is current on this thread
+    pub fn is_current(&self) -> bool {
+        unsafe { glfw_sys::glfwGetCurrentContext() == self.raw.as_ptr() }
+    }
+
+    /// Set the amount of frames to wait before swapping the buffers of this window.
+    /// Unlike [`Context::set_swap_interval`], this doesn't depend on which context
+    /// is current
+    ///
+    /// # Panics
+    /// If the context was split off with [`Window::render_context`]
+    ///
+    /// [`Context::set_swap_interval`]: crate::Context::set_swap_interval
+    pub fn set_swap_interval(&self, interval: i32) {
+        let _guard = self.make_current();
+        unsafe { glfw_sys::glfwSwapInterval(interval) };
+        state::with_state(self.raw.as_ptr(), |state| {
+            state.swap_interval = Some(interval);
+            if let Some(stats) = &mut state.frame_stats {
+                stats.set_swap_interval(interval);
+            }
+        });
+    }
+
     /// Get the loader function for OpenGL
     pub fn get_load_proc(&self) -> LoadProc {
-        |name| unsafe {
-            match glfw_sys::glfwGetProcAddress(name) {
-                Some(ptr) => ptr as *const c_void,
-                None => ptr::null_mut(),
-            }
-        }
+        load_proc
     }
 
     /// Get the loader function for OpenGL. This one is recommended when your
-    /// OpenGL loader requires a `fn(&str) -> *const c_void`
+    /// OpenGL loader requires a `fn(&str) -> *const c_void`, see
+    /// [`safe_load_proc`]
+    ///
+    /// [`safe_load_proc`]: crate::safe_load_proc
     pub fn get_safe_load_proc(&self) -> SafeLoadProc {
-        |name| unsafe {
-            let name = match CString::new(name) {
-                Ok(name) => name,
-                Err(_) => return ptr::null_mut(),
-            };
-            match glfw_sys::glfwGetProcAddress(name.as_ptr()) {
-                Some(ptr) => ptr as *const c_void,
-                None => ptr::null_mut(),
-            }
-        }
+        safe_load_proc
     }
 
     /// Swap the buffers of the window
+    ///
+    /// # Panics
+    /// If the context was split off with [`Window::render_context`], swap with
+    /// [`RenderContext::swap_buffers`] instead
     pub fn update(&self) {
+        self.assert_not_split();
         unsafe { glfw_sys::glfwSwapBuffers(self.raw.as_ptr()) }
+        state::with_state(self.raw.as_ptr(), |state| {
+            if let Some(stats) = &mut state.frame_stats {
+                stats.record_swap(GlfwInstant::now());
+            }
+        });
     }
 
+    /// Start collecting [`FrameStats`] on every [`Window::update`], keeping the
+    /// times of the last `capacity` frames. Missed vsync intervals are detected
+    /// using the refresh rate of the window's monitor, or of the primary monitor
+    /// for windowed mode windows, and the interval set with
+    /// [`Window::set_swap_interval`]
+    pub fn enable_frame_stats(&self, capacity: usize) {
+        let mut stats = FrameStats::new(capacity);
+        stats.set_refresh_rate(self.refresh_rate().unwrap_or(0));
+        state::with_state(self.raw.as_ptr(), |state| {
+            if let Some(interval) = state.swap_interval {
+                stats.set_swap_interval(interval);
+            }
+            state.frame_stats = Some(stats);
+        });
+    }
+
+    pub fn disable_frame_stats(&self) {
+        state::with_state(self.raw.as_ptr(), |state| state.frame_stats = None);
+    }
+
+    /// Get a copy of the frame statistics, or [`None`] if they are not enabled
+    pub fn frame_stats(&self) -> Option<FrameStats> {
+        state::with_state(self.raw.as_ptr(), |state| state.frame_stats.clone())
+    }
+
+    /// Print the frame statistics to stderr every `frames` frames, or stop
+    /// printing them if [`None`]. Does nothing if the statistics are not enabled
+    pub fn log_frame_stats(&self, frames: Option<u64>) {
+        state::with_state(self.raw.as_ptr(), |state| {
+            if let Some(stats) = &mut state.frame_stats {
+                stats.set_log_interval(frames);
+            }
+        });
+    }
+
+    fn refresh_rate(&self) -> Option<u32> {
+        let mut monitor = unsafe { glfw_sys::glfwGetWindowMonitor(self.raw.as_ptr()) };
+        if monitor.is_null() {
+            monitor = unsafe { glfw_sys::glfwGetPrimaryMonitor() };
+        }
+        if monitor.is_null() {
+            return None;
+        }
+
+        let vidmode = unsafe { glfw_sys::glfwGetVideoMode(monitor).as_ref()? };
+        vidmode.refreshRate.try_into().ok()
+    }
+}
+
+impl<C> Window<C> {
     /// Check if the window should close. Use this in a loop to check when the app
     /// should close
     pub fn should_close(&self) -> bool {
@@ -116,6 +342,220 @@ impl Window {
         res == 1
     }
 
+    /// Set the close flag of the window. Can be used to close the window
+    /// programmatically, or to cancel a close request
+    pub fn set_should_close(&self, value: bool) {
+        unsafe { glfw_sys::glfwSetWindowShouldClose(self.raw.as_ptr(), value as i32) }
+    }
+
+    /// Set the function which should be called when the user tries to close the
+    /// window, e.g. by clicking the close button. Return `false` from it to keep
+    /// the window open
+    pub fn set_close_handler<F: FnMut(&Window<C>) -> bool + 'static>(&self, mut handler: F)
+    where
+        C: 'static,
+    {
+        let handler = move |raw| handler(&unsafe { Window::from_raw_borrowed(raw) });
+        state::with_state(self.raw.as_ptr(), |state| {
+            state.close_handler = Some(Box::new(handler))
+        });
+    }
+
+    /// Set the title of the window. The title is cut at the first nul byte, if any
+    pub fn set_title<S: AsRef<str>>(&self, title: S) {
+        let title = title.as_ref();
+        let title = title.split('\0').next().unwrap_or(title);
+        let title = CString::new(title).expect("Nul bytes were removed");
+        unsafe { glfw_sys::glfwSetWindowTitle(self.raw.as_ptr(), title.as_ptr()) }
+    }
+
+    /// Get the title of the window. Returns [`Error::FeatureUnavailable`] if the
+    /// library is older than GLFW 3.4
+    pub fn title(&self) -> Result<String, Error> {
+        version::require(3, 4)?;
+        let title = unsafe { glfw_sys::glfwGetWindowTitle(self.raw.as_ptr()) };
+        // GLFW only fails when it isn't initialized
+        if title.is_null() {
+            return Err(Error::NotInitialized);
+        }
+
+        let title = unsafe { CStr::from_ptr(title) };
+        Ok(title.to_string_lossy().to_string())
+    }
+
+    /// Set the icon of the window. Multiple sizes of the same icon may be given,
+    /// the system picks the one closest to the size it needs. Passing an empty
+    /// slice resets the icon to the default one
+    pub fn set_icon(&self, images: &[IconImage]) {
+        let images: Vec<_> = images.iter().map(IconImage::as_raw).collect();
+        let ptr = if images.is_empty() {
+            ptr::null()
+        } else {
+            images.as_ptr()
+        };
+        unsafe { glfw_sys::glfwSetWindowIcon(self.raw.as_ptr(), images.len() as i32, ptr) }
+    }
+
+    /// Get the opacity of the whole window, from 0.0 to 1.0
+    pub fn opacity(&self) -> f32 {
+        unsafe { glfw_sys::glfwGetWindowOpacity(self.raw.as_ptr()) }
+    }
+
+    /// Set the opacity of the whole window, including its decorations. The value
+    /// is clamped between 0.0 (fully transparent) and 1.0 (fully opaque)
+    pub fn set_opacity(&self, opacity: f32) {
+        let opacity = opacity.clamp(0.0, 1.0);
+        unsafe { glfw_sys::glfwSetWindowOpacity(self.raw.as_ptr(), opacity) }
+    }
+
+    /// Check if the window actually has a transparent framebuffer. This can be
+    /// false even if it was requested with [`Context::transparent_framebuffer`]
+    ///
+    /// [`Context::transparent_framebuffer`]: crate::Context::transparent_framebuffer
+    pub fn has_transparent_framebuffer(&self) -> bool {
+        self.get_attribute(WindowAttribute::TransparentFramebuffer)
+    }
+
+    /// Ask [`Context::run`] to emit an [`Event::RedrawRequested`] for this window
+    /// after the current events are handled
+    ///
+    /// [`Context::run`]: crate::Context::run
+    /// [`Event::RedrawRequested`]: crate::Event::RedrawRequested
+    pub fn request_redraw(&self) {
+        state::with_state(self.raw.as_ptr(), |state| state.redraw_requested = true);
+    }
+
+    /// Iconify (minimize) the window
+    pub fn iconify(&self) {
+        unsafe { glfw_sys::glfwIconifyWindow(self.raw.as_ptr()) }
+    }
+
+    /// Restore the window if it was previously iconified or maximized
+    pub fn restore(&self) {
+        unsafe { glfw_sys::glfwRestoreWindow(self.raw.as_ptr()) }
+    }
+
+    /// Maximize the window
+    pub fn maximize(&self) {
+        unsafe { glfw_sys::glfwMaximizeWindow(self.raw.as_ptr()) }
+    }
+
+    /// Make the window visible if it was hidden
+    pub fn show(&self) {
+        unsafe { glfw_sys::glfwShowWindow(self.raw.as_ptr()) }
+    }
+
+    /// Hide the window
+    pub fn hide(&self) {
+        unsafe { glfw_sys::glfwHideWindow(self.raw.as_ptr()) }
+    }
+
+    /// Bring the window to the front and give it input focus
+    pub fn focus(&self) {
+        unsafe { glfw_sys::glfwFocusWindow(self.raw.as_ptr()) }
+    }
+
+    /// Request the user's attention, usually by flashing the taskbar entry
+    pub fn request_attention(&self) {
+        unsafe { glfw_sys::glfwRequestWindowAttention(self.raw.as_ptr()) }
+    }
+
+    /// Check if the window is iconified
+    pub fn is_iconified(&self) -> bool {
+        self.get_attribute(WindowAttribute::Iconified)
+    }
+
+    /// Check if the window is maximized
+    pub fn is_maximized(&self) -> bool {
+        self.get_attribute(WindowAttribute::Maximized)
+    }
+
+    /// Check if the window is visible
+    pub fn is_visible(&self) -> bool {
+        self.get_attribute(WindowAttribute::Visible)
+    }
+
+    /// Check if the window has input focus
+    pub fn is_focused(&self) -> bool {
+        self.get_attribute(WindowAttribute::Focused)
+    }
+
+    /// Check if the cursor is hovering over the content area of the window
+    pub fn is_hovered(&self) -> bool {
+        self.get_attribute(WindowAttribute::Hovered)
+    }
+
+    /// Check if a boolean window attribute is enabled
+    pub fn get_attribute<A: Into<WindowAttribute>>(&self, attrib: A) -> bool {
+        self.get_raw(attrib.into() as i32) == glfw_sys::GLFW_TRUE as i32
+    }
+
+    /// Get the current value of an integer window attribute
+    pub fn get_value(&self, attrib: ValueAttribute) -> i32 {
+        self.get_raw(attrib as i32)
+    }
+
+    fn get_raw(&self, attrib: i32) -> i32 {
+        unsafe { glfw_sys::glfwGetWindowAttrib(self.raw.as_ptr(), attrib) }
+    }
+
+    /// Get the client API of the window's context, [`ClientApi::None`] if it
+    /// has none
+    pub fn client_api(&self) -> ClientApi {
+        ClientApi::from_raw(self.get_raw(glfw_sys::GLFW_CLIENT_API as i32))
+            .unwrap_or(ClientApi::None)
+    }
+
+    /// Get the API the window's context was created with, [`None`] if it has no
+    /// context
+    pub fn context_creation_api(&self) -> Option<ContextCreationApi> {
+        if self.client_api() == ClientApi::None {
+            return None;
+        }
+        ContextCreationApi::from_raw(self.get_raw(glfw_sys::GLFW_CONTEXT_CREATION_API as i32))
+    }
+
+    /// Get the OpenGL profile of the window's context
+    pub fn opengl_profile(&self) -> OpenGlProfile {
+        OpenGlProfile::from_raw(self.get_raw(glfw_sys::GLFW_OPENGL_PROFILE as i32))
+            .unwrap_or(OpenGlProfile::Any)
+    }
+
+    /// Get the robustness strategy of the window's context
+    pub fn context_robustness(&self) -> ContextRobustness {
+        ContextRobustness::from_raw(self.get_raw(glfw_sys::GLFW_CONTEXT_ROBUSTNESS as i32))
+            .unwrap_or(ContextRobustness::None)
+    }
+
+    /// Get the release behavior of the window's context
+    pub fn release_behavior(&self) -> ReleaseBehavior {
+        ReleaseBehavior::from_raw(self.get_raw(glfw_sys::GLFW_CONTEXT_RELEASE_BEHAVIOR as i32))
+            .unwrap_or(ReleaseBehavior::Any)
+    }
+
+    /// Get the properties of the context the window was actually created with
+    pub fn context_info(&self) -> ContextInfo {
+        ContextInfo {
+            client_api: self.client_api(),
+            creation_api: self.context_creation_api(),
+            major: self.get_value(ValueAttribute::ContextVersionMajor),
+            minor: self.get_value(ValueAttribute::ContextVersionMinor),
+            revision: self.get_value(ValueAttribute::ContextRevision),
+            profile: self.opengl_profile(),
+            forward_compat: self.get_attribute(WindowAttribute::OpenGlForwardCompat),
+            debug: self.get_attribute(WindowAttribute::ContextDebug),
+            robustness: self.context_robustness(),
+            release_behavior: self.release_behavior(),
+            no_error: self.get_attribute(WindowAttribute::ContextNoError),
+        }
+    }
+
+    /// Change a window attribute. Only attributes listed in [`SettableAttribute`]
+    /// can be changed after the window was created
+    pub fn set_attribute(&self, attrib: SettableAttribute, value: bool) {
+        unsafe { glfw_sys::glfwSetWindowAttrib(self.raw.as_ptr(), attrib as i32, value as i32) }
+    }
+
     /// Set the function which should be called when a key is pressed
     pub fn set_key_callback(&self, callback: KeyCallback) {
         unsafe { glfw_sys::glfwSetKeyCallback(self.raw.as_ptr(), Some(callback)) };
@@ -0,0 +1,51 @@
+//! Render with OSMesa on the headless platform and read the buffers back, so it
+//! runs without a display or GPU. Skipped when GLFW or OSMesa is missing.
+#![cfg(feature = "osmesa")]
+
+use glfw_rs::{Context, ContextCreationApi, InitOptions, Platform};
+
+#[test]
+fn color_and_depth_readback() {
+    let ctx = match Context::init_with(InitOptions::default().platform(Platform::Null)) {
+        Ok(ctx) => ctx,
+        Err(err) => {
+            eprintln!("skipping, GLFW can't be initialized headless: {err:?}");
+            return;
+        }
+    };
+    ctx.context_creation_api(ContextCreationApi::OsMesa);
+    ctx.depth_bits(24);
+    let Some(window) = ctx.window("OSMesa", (64, 32)) else {
+        eprintln!("skipping, OSMesa is not available");
+        return;
+    };
+
+    window.make_global();
+    gl::load_with(window.get_safe_load_proc());
+    unsafe {
+        gl::ClearColor(1.0, 0.0, 0.0, 1.0);
+        gl::ClearDepth(0.5);
+        gl::Clear(gl::COLOR_BUFFER_BIT | gl::DEPTH_BUFFER_BIT);
+        gl::Finish();
+    }
+
+    let color = window
+        .osmesa_color_buffer()
+        .expect("the context should be created with OSMesa");
+    assert_eq!((color.width(), color.height()), (64, 32));
+    assert!(
+        color
+            .pixels()
+            .chunks_exact(4)
+            .all(|pixel| pixel == [255, 0, 0, 255])
+    );
+
+    let depth = window
+        .osmesa_depth_buffer()
+        .expect("the context should have a depth buffer");
+    assert_eq!(depth.depth_bits(), 24);
+    assert_eq!(depth.max_value(), 0xff_ffff);
+    // the stencil in the top byte is masked out
+    let half = depth.max_value() / 2;
+    assert!(depth.values().iter().all(|value| value.abs_diff(half) <= 1));
+}
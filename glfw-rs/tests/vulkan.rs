@@ -0,0 +1,49 @@
+//! Create a Vulkan surface for a window, skipped when GLFW can't be initialized,
+//! e.g. without a display, or when there's no Vulkan driver.
+#![cfg(feature = "vulkan")]
+
+use ash::vk;
+use glfw_rs::Context;
+
+#[test]
+fn create_and_destroy_surface() {
+    let ctx = match Context::init() {
+        Ok(ctx) => ctx,
+        Err(err) => {
+            eprintln!("skipping, GLFW can't be initialized: {err:?}");
+            return;
+        }
+    };
+    if !ctx.vulkan_supported() {
+        eprintln!("skipping, Vulkan is not available");
+        return;
+    }
+
+    let extensions = ctx
+        .required_instance_extensions()
+        .expect("Vulkan should be able to present to windows");
+    assert!(!extensions.is_empty());
+    let extensions: Vec<_> = extensions.iter().map(|name| name.as_ptr()).collect();
+
+    let entry = unsafe { ash::Entry::load() }.expect("the Vulkan loader should load");
+    let app_info = vk::ApplicationInfo::default().api_version(vk::API_VERSION_1_0);
+    let create_info = vk::InstanceCreateInfo::default()
+        .application_info(&app_info)
+        .enabled_extension_names(&extensions);
+    let instance = unsafe { entry.create_instance(&create_info, None) }
+        .expect("the instance should be created with the required extensions");
+
+    let window = ctx
+        .window_no_api("Vulkan", (64, 64))
+        .expect("the window should be created");
+    let surface = window
+        .create_surface(&instance, None)
+        .expect("the surface should be created");
+    assert_ne!(surface, vk::SurfaceKHR::null());
+
+    let surface_instance = ash::khr::surface::Instance::new(&entry, &instance);
+    unsafe {
+        surface_instance.destroy_surface(surface, None);
+        instance.destroy_instance(None);
+    }
+}